@@ -0,0 +1,39 @@
+use std::{env, fs, path::Path};
+
+// Generates one cargo test per file in examples/ so that adding an example
+// only needs the input and its sidecar answers. See tests/examples.rs.
+fn main() {
+    println!("cargo:rerun-if-changed=examples");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("Cargo sets CARGO_MANIFEST_DIR.");
+    let examples_dir = Path::new(&manifest_dir).join("examples");
+    let mut names: Vec<String> = fs::read_dir(&examples_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+                .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+
+    let tests: String = names
+        .iter()
+        .map(|name| {
+            let test_name: String = name
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect();
+            format!(
+                "#[test]\nfn example_{}() {{\n    advent_of_code_2023::examples::assert_example({:?});\n}}\n\n",
+                test_name,
+                examples_dir.join(format!("{}.txt", name)).display().to_string()
+            )
+        })
+        .collect();
+
+    let out_dir = env::var("OUT_DIR").expect("Cargo sets OUT_DIR.");
+    fs::write(Path::new(&out_dir).join("examples.rs"), tests)
+        .expect("Could not write generated example tests.");
+}
@@ -0,0 +1,103 @@
+use std::fmt::Display;
+
+use num::BigInt;
+
+use crate::challenge::NOT_IMPLEMENTED;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(BigInt),
+    Text(String),
+    NotImplemented,
+}
+
+impl Answer {
+    // Reads an answer back from its displayed form, e.g. from an answers file.
+    pub fn parse(text: &str) -> Self {
+        if text == NOT_IMPLEMENTED {
+            return Answer::NotImplemented;
+        }
+
+        match text.parse::<BigInt>() {
+            Ok(value) => Answer::Integer(value),
+            Err(_) => Answer::Text(text.to_string()),
+        }
+    }
+
+    pub fn is_implemented(&self) -> bool {
+        *self != Answer::NotImplemented
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{}", value),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::NotImplemented => write!(f, "{}", NOT_IMPLEMENTED),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($integer:ty),*) => {
+        $(
+            impl From<$integer> for Answer {
+                fn from(value: $integer) -> Self {
+                    Answer::Integer(BigInt::from(value))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(i32, i64, i128, u32, u64, u128, usize);
+
+impl From<BigInt> for Answer {
+    fn from(value: BigInt) -> Self {
+        Answer::Integer(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        *self == Answer::parse(other)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn answer_parse() {
+        assert_eq!(Answer::parse("288"), Answer::from(288));
+        assert_eq!(Answer::parse("-5"), Answer::from(-5));
+        assert_eq!(
+            Answer::parse("10818234074807"),
+            Answer::from(10818234074807u64)
+        );
+        assert_eq!(Answer::parse("ABC"), Answer::from("ABC"));
+        assert_eq!(Answer::parse(NOT_IMPLEMENTED), Answer::NotImplemented);
+    }
+
+    #[test]
+    fn answer_compares_values() {
+        assert_eq!(Answer::parse("035"), Answer::from(35));
+        assert_eq!(Answer::from(35), "35");
+        assert!(!Answer::NotImplemented.is_implemented());
+        assert_eq!(Answer::NotImplemented.to_string(), NOT_IMPLEMENTED);
+    }
+}
@@ -0,0 +1,522 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    answer::Answer,
+    challenge::{Challenge, ChallengeFactory, DEFAULT_SOLVER},
+    context::SolveContext,
+    error::ChallengeError,
+    runner::{self, RunOptions},
+    utils::parse_toml_table,
+};
+
+pub const DEFAULT_BASELINE_PATH: &str = "./bench.toml";
+
+pub struct BenchOptions {
+    pub warmup: usize,
+    pub iterations: usize,
+    // Percentage slowdown of the median allowed before a phase counts as a
+    // regression.
+    pub threshold: f64,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            warmup: 10,
+            iterations: 100,
+            threshold: 10.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let count = sorted.len();
+        let median = if count % 2 == 0 {
+            (sorted[count / 2 - 1] + sorted[count / 2]) / 2
+        } else {
+            sorted[count / 2]
+        };
+        // Nearest-rank percentile.
+        let p95 = sorted[(count * 95).div_ceil(100) - 1];
+
+        Some(Stats {
+            min: sorted[0],
+            median,
+            mean: sorted.iter().sum::<Duration>() / count as u32,
+            p95,
+        })
+    }
+}
+
+pub struct PartBench {
+    pub part: u8,
+    pub answer: Answer,
+    pub stats: Stats,
+}
+
+pub struct BenchReport {
+    pub year: u16,
+    pub challenge: u8,
+    // The solver that was measured, with the default resolved to its name.
+    pub solver: String,
+    pub warmup: usize,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub parts: Vec<PartBench>,
+}
+
+impl BenchReport {
+    fn phases(&self) -> Vec<(String, Duration)> {
+        let mut phases = vec![(String::from("parse"), self.parse.median)];
+        phases.extend(
+            self.parts
+                .iter()
+                .map(|part| (format!("part{}", part.part), part.stats.median)),
+        );
+        phases
+    }
+}
+
+pub fn measure(
+    challenge: u8,
+    input: &[u8],
+    run_options: &RunOptions,
+    options: &BenchOptions,
+) -> Result<BenchReport, ChallengeError> {
+    // Parsing is sampled like the parts, so its median is as stable.
+    let create = || ChallengeFactory::create(run_options.year, &challenge, &mut &input[..]);
+    let mut solution = create()?;
+    for _ in 0..options.warmup {
+        create()?;
+    }
+    let mut parse_samples = Vec::with_capacity(options.iterations);
+    for _ in 0..options.iterations {
+        let start = Instant::now();
+        solution = create()?;
+        parse_samples.push(start.elapsed());
+    }
+    let parse = Stats::from(&parse_samples)
+        .ok_or_else(|| ChallengeError::solve("At least one iteration is required"))?;
+
+    let solver = match &run_options.solver {
+        Some(solver) => {
+            solution
+                .use_solver(solver)
+                .map_err(|e| e.with_day(challenge))?;
+            solver.clone()
+        }
+        None => solution
+            .solvers()
+            .first()
+            .copied()
+            .unwrap_or(DEFAULT_SOLVER)
+            .to_string(),
+    };
+
+    let mut parts = Vec::new();
+    for part in [1, 2] {
+        if !run_options.includes_part(part) {
+            continue;
+        }
+
+        let context = SolveContext::default();
+        let solve = |solution: &dyn Challenge| match part {
+            1 => solution.solve_part_one(&context),
+            _ => solution.solve_part_two(&context),
+        };
+        for _ in 0..options.warmup {
+            solve(solution.as_ref()).map_err(|e| e.with_day(challenge))?;
+        }
+
+        let mut answer = None;
+        let mut samples = Vec::with_capacity(options.iterations);
+        for _ in 0..options.iterations {
+            let start = Instant::now();
+            answer = Some(solve(solution.as_ref()).map_err(|e| e.with_day(challenge))?);
+            samples.push(start.elapsed());
+        }
+
+        let (Some(answer), Some(stats)) = (answer, Stats::from(&samples)) else {
+            return Err(ChallengeError::solve("At least one iteration is required"));
+        };
+        parts.push(PartBench {
+            part,
+            answer,
+            stats,
+        });
+    }
+
+    Ok(BenchReport {
+        year: run_options.year,
+        challenge,
+        solver,
+        warmup: options.warmup,
+        iterations: options.iterations,
+        parse,
+        parts,
+    })
+}
+
+// Median nanoseconds per year, day, solver and phase, e.g. `[2023.8.lcm]`
+// followed by `parse = 1200` and `part2 = 5400`. Each solver is only compared
+// against its own timings.
+#[derive(Debug, Default, PartialEq)]
+pub struct Baseline {
+    entries: BTreeMap<BaselineKey, u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BaselineKey {
+    pub year: u16,
+    pub day: u8,
+    pub solver: String,
+    pub phase: String,
+}
+
+impl BaselineKey {
+    pub fn new(year: u16, day: u8, solver: &str, phase: &str) -> Self {
+        BaselineKey {
+            year,
+            day,
+            solver: solver.to_string(),
+            phase: phase.to_string(),
+        }
+    }
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Self, ChallengeError> {
+        if !path.exists() {
+            return Ok(Baseline::default());
+        }
+
+        let content = fs::read_to_string(path).map_err(|e| {
+            ChallengeError::io(&format!("Could not read {}: {}", path.display(), e))
+        })?;
+        Baseline::parse(&content)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ChallengeError> {
+        fs::write(path, self.to_toml())
+            .map_err(|e| ChallengeError::io(&format!("Could not write {}: {}", path.display(), e)))
+    }
+
+    pub fn get(&self, key: &BaselineKey) -> Option<Duration> {
+        self.entries
+            .get(key)
+            .map(|nanos| Duration::from_nanos(*nanos))
+    }
+
+    pub fn insert(&mut self, key: BaselineKey, median: Duration) {
+        self.entries.insert(key, median.as_nanos() as u64);
+    }
+
+    pub fn record(&mut self, report: &BenchReport) {
+        for (phase, median) in report.phases() {
+            self.insert(
+                BaselineKey::new(report.year, report.challenge, &report.solver, &phase),
+                median,
+            );
+        }
+    }
+
+    fn parse(content: &str) -> Result<Self, ChallengeError> {
+        let table = parse_toml_table(content)?;
+
+        let mut baseline = Baseline::default();
+        for (year_key, days) in &table {
+            let year = year_key
+                .parse::<u16>()
+                .map_err(|_| ChallengeError::parse(year_key, 1, "Could not parse year"))?;
+            for (day_key, solvers) in subtable(year_key, days)? {
+                let day = day_key
+                    .parse::<u8>()
+                    .map_err(|_| ChallengeError::parse(day_key, 1, "Could not parse day"))?;
+                for (solver, phases) in subtable(day_key, solvers)? {
+                    for (phase, nanos) in subtable(solver, phases)? {
+                        let nanos = nanos
+                            .as_integer()
+                            .and_then(|nanos| u64::try_from(nanos).ok())
+                            .ok_or_else(|| {
+                                ChallengeError::parse(
+                                    &nanos.to_string(),
+                                    1,
+                                    "Expected a non-negative number of nanoseconds",
+                                )
+                            })?;
+                        baseline
+                            .entries
+                            .insert(BaselineKey::new(year, day, solver, phase), nanos);
+                    }
+                }
+            }
+        }
+
+        Ok(baseline)
+    }
+
+    fn to_toml(&self) -> String {
+        let mut table = toml::Table::new();
+        for (key, nanos) in &self.entries {
+            let mut entry = &mut table;
+            for name in [
+                key.year.to_string(),
+                key.day.to_string(),
+                key.solver.clone(),
+            ] {
+                entry = entry
+                    .entry(name)
+                    .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+                    .as_table_mut()
+                    .expect("Year, day and solver entries are always tables.");
+            }
+            entry.insert(key.phase.clone(), toml::Value::Integer(*nanos as i64));
+        }
+
+        table.to_string()
+    }
+}
+
+fn subtable<'a>(key: &str, value: &'a toml::Value) -> Result<&'a toml::Table, ChallengeError> {
+    value
+        .as_table()
+        .ok_or_else(|| ChallengeError::parse(key, 1, "Expected a table"))
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Comparison {
+    pub phase: String,
+    pub baseline: Duration,
+    pub current: Duration,
+    pub regressed: bool,
+}
+
+impl Comparison {
+    pub fn change(&self) -> f64 {
+        (self.current.as_secs_f64() / self.baseline.as_secs_f64() - 1.0) * 100.0
+    }
+}
+
+pub fn compare(report: &BenchReport, baseline: &Baseline, threshold: f64) -> Vec<Comparison> {
+    report
+        .phases()
+        .into_iter()
+        .filter_map(|(phase, current)| {
+            let key = BaselineKey::new(report.year, report.challenge, &report.solver, &phase);
+            let previous = baseline.get(&key)?;
+            Some(Comparison {
+                regressed: current.as_secs_f64()
+                    > previous.as_secs_f64() * (1.0 + threshold / 100.0),
+                phase,
+                baseline: previous,
+                current,
+            })
+        })
+        .collect()
+}
+
+pub fn bench(
+    challenge: u8,
+    input_path: &str,
+    run_options: &RunOptions,
+    options: &BenchOptions,
+    baseline_path: &Path,
+    save: bool,
+) -> Result<bool, ChallengeError> {
+    let input = runner::read_input(input_path).map_err(|e| {
+        ChallengeError::io(&format!("Could not read {}: {}", input_path, e)).with_day(challenge)
+    })?;
+    let report = measure(challenge, &input, run_options, options)?;
+
+    let mut baseline = Baseline::load(baseline_path)?;
+    let comparisons = compare(&report, &baseline, options.threshold);
+    print_report(&report, &comparisons);
+
+    if save {
+        baseline.record(&report);
+        baseline.save(baseline_path)?;
+        println!("Saved baseline to {}", baseline_path.display());
+    }
+
+    Ok(!comparisons.iter().any(|comparison| comparison.regressed))
+}
+
+pub fn print_report(report: &BenchReport, comparisons: &[Comparison]) {
+    println!(
+        "Day {} ({} solver): {} iterations after {} warm-up",
+        report.challenge, report.solver, report.iterations, report.warmup
+    );
+    println!(
+        "Parse: min {:.2?}, median {:.2?}, mean {:.2?}, p95 {:.2?}",
+        report.parse.min, report.parse.median, report.parse.mean, report.parse.p95
+    );
+    for part in &report.parts {
+        println!(
+            "Part {}: min {:.2?}, median {:.2?}, mean {:.2?}, p95 {:.2?} (answer {})",
+            part.part,
+            part.stats.min,
+            part.stats.median,
+            part.stats.mean,
+            part.stats.p95,
+            part.answer
+        );
+    }
+
+    for comparison in comparisons {
+        let status = if comparison.regressed {
+            "REGRESSION"
+        } else {
+            "ok"
+        };
+        println!(
+            "{} vs baseline: {:.2?} -> {:.2?} ({:+.1}%) {}",
+            comparison.phase,
+            comparison.baseline,
+            comparison.current,
+            comparison.change(),
+            status
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|ms| Duration::from_millis(*ms)).collect()
+    }
+
+    #[test]
+    fn bench_stats() {
+        let samples = millis(&(1..=20).rev().collect::<Vec<u64>>());
+        let stats = Stats::from(&samples).unwrap();
+
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(10500));
+        assert_eq!(stats.mean, Duration::from_micros(10500));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+    }
+
+    #[test]
+    fn bench_stats_single_sample() {
+        let stats = Stats::from(&millis(&[7])).unwrap();
+
+        assert_eq!(stats.min, Duration::from_millis(7));
+        assert_eq!(stats.median, Duration::from_millis(7));
+        assert_eq!(stats.p95, Duration::from_millis(7));
+        assert_eq!(Stats::from(&[]), None);
+    }
+
+    #[test]
+    fn bench_measure() {
+        let input = "Time:      7  15   30\nDistance:  9  40  200";
+        let options = BenchOptions {
+            warmup: 1,
+            iterations: 3,
+            ..BenchOptions::default()
+        };
+        let report = measure(6, input.as_bytes(), &RunOptions::default(), &options).unwrap();
+
+        assert_eq!(report.solver, "default");
+        assert_eq!(report.parts.len(), 2);
+        assert_eq!(report.parts[0].answer, "288");
+        assert_eq!(report.parts[1].answer, "71503");
+    }
+
+    #[test]
+    fn bench_measure_selected_part() {
+        let input = "Time:      7  15   30\nDistance:  9  40  200";
+        let run_options = RunOptions {
+            part: Some(2),
+            ..RunOptions::default()
+        };
+        let report = measure(6, input.as_bytes(), &run_options, &BenchOptions::default()).unwrap();
+
+        assert_eq!(report.parts.len(), 1);
+        assert_eq!(report.parts[0].part, 2);
+    }
+
+    #[test]
+    fn bench_baseline_round_trip() {
+        let key = |solver, phase| BaselineKey::new(2023, 8, solver, phase);
+        let mut baseline = Baseline::default();
+        baseline.insert(key("lcm", "parse"), Duration::from_nanos(1200));
+        baseline.insert(key("lcm", "part2"), Duration::from_nanos(5400));
+        baseline.insert(key("brute-force", "part2"), Duration::from_nanos(98000));
+
+        assert_eq!(Baseline::parse(&baseline.to_toml()).unwrap(), baseline);
+        assert_eq!(
+            baseline.get(&key("lcm", "part2")),
+            Some(Duration::from_nanos(5400))
+        );
+        assert_eq!(baseline.get(&key("lcm", "part1")), None);
+        assert_eq!(
+            baseline.get(&BaselineKey::new(2022, 8, "lcm", "part2")),
+            None
+        );
+    }
+
+    #[test]
+    fn bench_baseline_rejects_negative() {
+        assert!(Baseline::parse("[2023.5.default]\nparse = -1").is_err());
+        assert!(Baseline::parse("[2023.5]\nparse = 1").is_err());
+    }
+
+    #[test]
+    fn bench_compare_flags_regressions() {
+        let stats = |ms| Stats::from(&millis(&[ms])).unwrap();
+        let report = BenchReport {
+            year: 2023,
+            challenge: 5,
+            solver: String::from("default"),
+            warmup: 0,
+            iterations: 1,
+            parse: stats(10),
+            parts: vec![
+                PartBench {
+                    part: 1,
+                    answer: Answer::from(35),
+                    stats: stats(105),
+                },
+                PartBench {
+                    part: 2,
+                    answer: Answer::from(46),
+                    stats: stats(120),
+                },
+            ],
+        };
+        let mut baseline = Baseline::default();
+        let key = |solver, phase| BaselineKey::new(2023, 5, solver, phase);
+        baseline.insert(key("default", "part1"), Duration::from_millis(100));
+        baseline.insert(key("default", "part2"), Duration::from_millis(100));
+        baseline.insert(key("brute-force", "parse"), Duration::from_millis(1));
+
+        let comparisons = compare(&report, &baseline, 10.0);
+
+        assert_eq!(comparisons.len(), 2);
+        assert!(!comparisons[0].regressed);
+        assert!(comparisons[1].regressed);
+        assert!((comparisons[1].change() - 20.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,161 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{answer::Answer, challenge::ChallengeFactory, error::ChallengeError};
+
+pub const DEFAULT_CACHE_DIR: &str = "./.aoc-cache";
+
+// Identifies one answer. A new input, solver or solver version gives a new
+// key, so stale answers are never read back.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheKey {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub solver: String,
+    pub version: u32,
+    pub input_hash: String,
+}
+
+impl CacheKey {
+    // None for unregistered days and unknown solvers, which are never cached.
+    pub fn new(
+        year: u16,
+        day: u8,
+        part: u8,
+        solver: Option<&str>,
+        input_hash: &str,
+    ) -> Option<Self> {
+        let registration = ChallengeFactory::registration(year, &day)?;
+        let solvers = ChallengeFactory::solvers(year, &day)?;
+        let solver = match solver {
+            Some(solver) => *solvers.iter().find(|name| **name == solver)?,
+            None => *solvers.first()?,
+        };
+
+        Some(CacheKey {
+            year,
+            day,
+            part,
+            solver: solver.to_string(),
+            version: registration.version,
+            input_hash: input_hash.to_string(),
+        })
+    }
+}
+
+// Answers are stored one per file as
+// `<dir>/<year>/<day>/part<part>-<solver>-v<version>-<input hash>`.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: &Path) -> Self {
+        Cache {
+            dir: dir.to_path_buf(),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, key: &CacheKey) -> PathBuf {
+        self.dir
+            .join(key.year.to_string())
+            .join(key.day.to_string())
+            .join(format!(
+                "part{}-{}-v{}-{}",
+                key.part, key.solver, key.version, key.input_hash
+            ))
+    }
+
+    pub fn get(&self, key: &CacheKey) -> Option<Answer> {
+        let content = fs::read_to_string(self.path(key)).ok()?;
+        Some(Answer::parse(content.trim_end_matches('\n')))
+    }
+
+    pub fn put(&self, key: &CacheKey, answer: &Answer) -> io::Result<()> {
+        let path = self.path(key);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, format!("{}\n", answer))
+    }
+
+    // Returns how many answers were removed.
+    pub fn clear(&self) -> Result<usize, ChallengeError> {
+        let error = |e: io::Error| {
+            ChallengeError::io(&format!("Could not clear {}: {}", self.dir.display(), e))
+        };
+        if !self.dir.exists() {
+            return Ok(0);
+        }
+
+        let count = count_files(&self.dir).map_err(error)?;
+        fs::remove_dir_all(&self.dir).map_err(error)?;
+
+        Ok(count)
+    }
+}
+
+fn count_files(dir: &Path) -> io::Result<usize> {
+    let mut count = 0;
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        count += if path.is_dir() {
+            count_files(&path)?
+        } else {
+            1
+        };
+    }
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::temp_dir::TempDir;
+
+    #[test]
+    fn cache_key_resolves_default_solver() {
+        let key = CacheKey::new(2023, 8, 2, None, "00000000000000ff").unwrap();
+
+        assert_eq!(key.solver, "lcm");
+        assert_eq!(key.version, 1);
+        assert_eq!(
+            CacheKey::new(2023, 8, 2, Some("brute-force"), "ff")
+                .unwrap()
+                .solver,
+            "brute-force"
+        );
+        assert_eq!(CacheKey::new(2023, 8, 2, Some("fast"), "ff"), None);
+        assert_eq!(CacheKey::new(2023, 25, 1, None, "ff"), None);
+    }
+
+    #[test]
+    fn cache_round_trips_answers() {
+        let dir = TempDir::new("cache-round-trip");
+        let cache = Cache::new(&dir);
+        let key = CacheKey::new(2023, 6, 1, None, "00000000000000ff").unwrap();
+        let other_input = CacheKey {
+            input_hash: String::from("0000000000000001"),
+            ..key.clone()
+        };
+
+        assert_eq!(cache.get(&key), None);
+        cache.put(&key, &Answer::from(288)).unwrap();
+        assert_eq!(cache.get(&key), Some(Answer::from(288)));
+        assert_eq!(cache.get(&other_input), None);
+
+        assert_eq!(cache.clear().unwrap(), 1);
+        assert_eq!(cache.get(&key), None);
+        assert_eq!(cache.clear().unwrap(), 0);
+    }
+}
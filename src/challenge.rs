@@ -1,41 +1,180 @@
-use std::fs::File;
-
-mod camel_cards;
-mod cube_conundrum;
-mod gear_ratios;
-mod haunted_wasteland;
-mod if_you_give_a_seed_a_fertilizer;
-mod mirage_maintenance;
-mod pipe_maze;
-mod scratchcards;
-mod trebuchet;
-mod wait_for_it;
+use std::io::BufRead;
+
+use crate::{answer::Answer, context::SolveContext, error::ChallengeError, utils::read_normalized};
+
+// Each day module declares its own `REGISTRATION`. Listing the module here
+// declares it and adds it to the registry.
+macro_rules! register {
+    ($($module:ident),* $(,)?) => {
+        $(pub mod $module;)*
+
+        const REGISTRY: &[&Registration] = &[$(&$module::REGISTRATION),*];
+    };
+}
+
+register!(
+    trebuchet,
+    cube_conundrum,
+    gear_ratios,
+    scratchcards,
+    if_you_give_a_seed_a_fertilizer,
+    wait_for_it,
+    camel_cards,
+    haunted_wasteland,
+    mirage_maintenance,
+    pipe_maze,
+);
+
+pub const NOT_IMPLEMENTED: &str = "Not implemented yet!";
+pub const DEFAULT_SOLVER: &str = "default";
+pub const DEFAULT_YEAR: u16 = 2023;
 
 pub trait Challenge {
-    fn solve_part_one(&self) -> String;
-    fn solve_part_two(&self) -> String;
-    fn load(&mut self, file: &File);
+    fn solve_part_one(&self, context: &SolveContext) -> Result<Answer, ChallengeError>;
+    fn solve_part_two(&self, context: &SolveContext) -> Result<Answer, ChallengeError>;
+    fn load(&mut self, reader: &mut dyn BufRead) -> Result<(), ChallengeError>;
+
+    fn load_str(&mut self, input: &str) -> Result<(), ChallengeError> {
+        self.load(&mut input.as_bytes())
+    }
+
+    // The first solver listed is the one used unless another is selected.
+    fn solvers(&self) -> Vec<&'static str> {
+        vec![DEFAULT_SOLVER]
+    }
+
+    fn use_solver(&mut self, name: &str) -> Result<(), ChallengeError> {
+        if self.solvers().contains(&name) {
+            Ok(())
+        } else {
+            Err(ChallengeError::unknown_solver(name, &self.solvers()))
+        }
+    }
+
+    // Day-specific REPL commands as usage strings, e.g. "hand <cards>". The
+    // first word is the command passed to `query`.
+    fn queries(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    fn query(&self, command: &str, _args: &[&str]) -> Result<String, ChallengeError> {
+        Err(ChallengeError::unknown_command(command, &self.queries()))
+    }
+
+    // How a single input line is parsed, shown by the REPL's `line` command.
+    // Days whose lines only make sense together return None.
+    fn parse_line(&self, _line: &str) -> Option<Result<String, ChallengeError>> {
+        None
+    }
+}
+
+pub struct Registration {
+    pub year: u16,
+    pub day: u8,
+    pub title: &'static str,
+    // Bump when a change could alter this day's answers, so cached answers
+    // from the earlier solver are no longer used.
+    pub version: u32,
+    pub create: fn() -> Box<dyn Challenge>,
 }
 
 pub struct ChallengeFactory;
 
 impl ChallengeFactory {
-    pub fn create(challenge_id: &u8, file: &File) -> Box<dyn Challenge> {
-        let mut solution: Box<dyn Challenge> = match challenge_id {
-            1 => Box::new(trebuchet::Trebuchet::default()),
-            2 => Box::new(cube_conundrum::CubeConundrum::default()),
-            3 => Box::new(gear_ratios::GearRatios::default()),
-            4 => Box::new(scratchcards::Scratchcards::default()),
-            5 => Box::new(if_you_give_a_seed_a_fertilizer::IfYouGiveASeedAFertilizer::default()),
-            6 => Box::new(wait_for_it::WaitForIt::default()),
-            7 => Box::new(camel_cards::CamelCards::default()),
-            8 => Box::new(haunted_wasteland::HauntedWasteland::default()),
-            9 => Box::new(mirage_maintenance::MirageMaintenance::default()),
-            10 => Box::new(pipe_maze::PipeMaze::default()),
-            _ => panic!("Challenge {} not implemented yet!", challenge_id),
-        };
-
-        solution.load(file);
+    pub fn create(
+        year: u16,
+        challenge_id: &u8,
+        reader: &mut dyn BufRead,
+    ) -> Result<Box<dyn Challenge>, ChallengeError> {
+        let mut solution = ChallengeFactory::new_challenge(year, challenge_id).ok_or(
+            ChallengeError::Unregistered {
+                year,
+                day: *challenge_id,
+            },
+        )?;
+
+        let input = read_normalized(reader).map_err(|e| e.with_day(*challenge_id))?;
         solution
+            .load(&mut input.as_bytes())
+            .map_err(|e| e.with_day(*challenge_id))?;
+        Ok(solution)
+    }
+
+    pub fn solvers(year: u16, challenge_id: &u8) -> Option<Vec<&'static str>> {
+        ChallengeFactory::new_challenge(year, challenge_id).map(|solution| solution.solvers())
+    }
+
+    pub fn is_registered(year: u16, challenge_id: &u8) -> bool {
+        ChallengeFactory::registration(year, challenge_id).is_some()
+    }
+
+    pub fn years() -> Vec<u16> {
+        let mut years: Vec<u16> = REGISTRY
+            .iter()
+            .map(|registration| registration.year)
+            .collect();
+        years.sort();
+        years.dedup();
+        years
+    }
+
+    pub fn registered(year: u16) -> Vec<u8> {
+        ChallengeFactory::registrations()
+            .iter()
+            .filter(|registration| registration.year == year)
+            .map(|registration| registration.day)
+            .collect()
+    }
+
+    pub fn registration(year: u16, challenge_id: &u8) -> Option<&'static Registration> {
+        REGISTRY
+            .iter()
+            .find(|registration| registration.year == year && registration.day == *challenge_id)
+            .copied()
+    }
+
+    // Every registration across all years, ordered by year and then day.
+    pub fn registrations() -> Vec<&'static Registration> {
+        let mut registrations = REGISTRY.to_vec();
+        registrations.sort_by_key(|registration| (registration.year, registration.day));
+        registrations
+    }
+
+    fn new_challenge(year: u16, challenge_id: &u8) -> Option<Box<dyn Challenge>> {
+        ChallengeFactory::registration(year, challenge_id)
+            .map(|registration| (registration.create)())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn challenge_registry_days_are_unique() {
+        let mut keys: Vec<(u16, u8)> = REGISTRY
+            .iter()
+            .map(|registration| (registration.year, registration.day))
+            .collect();
+        keys.sort();
+        keys.dedup();
+
+        assert_eq!(keys.len(), REGISTRY.len());
+        let days: Vec<u8> = keys
+            .iter()
+            .filter(|(year, _)| *year == DEFAULT_YEAR)
+            .map(|(_, day)| *day)
+            .collect();
+        assert_eq!(ChallengeFactory::registered(DEFAULT_YEAR), days);
+    }
+
+    #[test]
+    fn challenge_registration_lookup() {
+        let registration = ChallengeFactory::registration(2023, &10).unwrap();
+
+        assert_eq!(registration.title, "Pipe Maze");
+        assert!(ChallengeFactory::registration(2023, &25).is_none());
+        assert!(ChallengeFactory::registration(2022, &10).is_none());
+        assert_eq!(ChallengeFactory::years(), vec![2023]);
     }
 }
@@ -1,5 +1,7 @@
 use std::fmt::Display;
 
+use crate::error::ChallengeError;
+
 #[derive(Debug, Eq, PartialEq, PartialOrd, Hash, Ord, Clone, Copy)]
 pub enum Card {
     CJoker,
@@ -19,8 +21,8 @@ pub enum Card {
 }
 
 impl Card {
-    pub fn from(ch: char) -> Self {
-        match ch {
+    pub fn from(ch: char) -> Result<Self, ChallengeError> {
+        let card = match ch {
             '2' => Card::C2,
             '3' => Card::C3,
             '4' => Card::C4,
@@ -34,13 +36,21 @@ impl Card {
             'Q' => Card::CQ,
             'K' => Card::CK,
             'A' => Card::CA,
-            _ => panic!("Attempted to parse invalid card."),
-        }
+            _ => {
+                return Err(ChallengeError::parse(
+                    &ch.to_string(),
+                    1,
+                    "Attempted to parse invalid card.",
+                ))
+            }
+        };
+
+        Ok(card)
     }
 
-    pub fn from_with_joker(ch: char) -> Self {
+    pub fn from_with_joker(ch: char) -> Result<Self, ChallengeError> {
         if ch == 'J' {
-            return Card::CJoker;
+            return Ok(Card::CJoker);
         }
 
         Card::from(ch)
@@ -76,30 +86,42 @@ mod test {
 
     #[test]
     fn ch07_from() {
-        let card = Card::from('A');
+        let card = Card::from('A').unwrap();
         assert_eq!(card, Card::CA)
     }
 
     #[test]
     fn ch07_ord() {
         let mut cards = vec![
-            Card::from('T'),
-            Card::from('K'),
-            Card::from('3'),
-            Card::from('7'),
+            Card::from('T').unwrap(),
+            Card::from('K').unwrap(),
+            Card::from('3').unwrap(),
+            Card::from('7').unwrap(),
         ];
 
         let expected = vec![
-            Card::from('3'),
-            Card::from('7'),
-            Card::from('T'),
-            Card::from('K'),
+            Card::from('3').unwrap(),
+            Card::from('7').unwrap(),
+            Card::from('T').unwrap(),
+            Card::from('K').unwrap(),
         ];
 
         cards.sort();
 
         assert_eq!(cards, expected);
 
-        assert!(Card::from('K') > Card::from('J'));
+        assert!(Card::from('K').unwrap() > Card::from('J').unwrap());
+    }
+
+    #[test]
+    fn ch07_from_invalid() {
+        assert_eq!(
+            Card::from('X'),
+            Err(ChallengeError::parse(
+                "X",
+                1,
+                "Attempted to parse invalid card."
+            ))
+        );
     }
 }
@@ -1,52 +1,62 @@
 use super::{card::Card, hand_type::HandType};
+use crate::error::ChallengeError;
 
-#[derive(Debug, Eq, PartialEq, Ord, Clone)]
+#[derive(Debug, Eq, PartialEq, Clone)]
 pub struct Hand {
-    cards: Vec<Card>,
+    cards: [Card; 5],
     hand_type: HandType,
     pub bid: u32,
 }
 
 impl Hand {
-    pub fn parse(line: String, with_joker: bool) -> Self {
-        let mut line_iter = line.split(' ');
-        let cards = line_iter
-            .next()
-            .expect("Could not find hand string.")
-            .chars()
-            .map(|c| {
-                if with_joker {
-                    return Card::from_with_joker(c);
-                }
-                Card::from(c)
+    pub fn parse(line: &str, with_joker: bool) -> Result<Self, ChallengeError> {
+        let (hand_str, bid_str) = line
+            .split_once(' ')
+            .ok_or_else(|| ChallengeError::parse(line, 1, "Could not find bid string."))?;
+        let cards = hand_str
+            .char_indices()
+            .map(|(i, c)| {
+                let card = if with_joker {
+                    Card::from_with_joker(c)
+                } else {
+                    Card::from(c)
+                };
+                card.map_err(|e| e.offset_columns(i))
             })
-            .collect::<Vec<Card>>();
-        let bid = line_iter
-            .next()
-            .expect("Could not find bid string.")
-            .parse::<u32>()
-            .expect("Could not convert bid to number.");
+            .collect::<Result<Vec<Card>, ChallengeError>>()?;
+        let cards: [Card; 5] = cards.try_into().map_err(|_| {
+            ChallengeError::parse(hand_str, 1, "Expected a hand of five cards.")
+        })?;
+        let bid_column = line.len() - bid_str.trim_start().len() + 1;
+        let bid_str = bid_str.trim();
+        let bid = bid_str.parse::<u32>().map_err(|_| {
+            ChallengeError::parse(bid_str, bid_column, "Could not convert bid to number.")
+        })?;
         let hand_type = HandType::parse(&cards);
 
-        Hand {
+        Ok(Hand {
             cards,
             hand_type,
             bid,
-        }
+        })
+    }
+
+    pub fn hand_type(&self) -> HandType {
+        self.hand_type
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.hand_type
+            .cmp(&other.hand_type)
+            .then_with(|| self.cards.cmp(&other.cards))
     }
 }
 
 impl PartialOrd for Hand {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        match self.hand_type.partial_cmp(&other.hand_type) {
-            Some(core::cmp::Ordering::Equal) => {}
-            ord => return ord,
-        }
-        match self.cards.iter().partial_cmp(other.cards.iter()) {
-            Some(core::cmp::Ordering::Equal) => {}
-            ord => return ord,
-        }
-        Some(core::cmp::Ordering::Equal)
+        Some(self.cmp(other))
     }
 }
 
@@ -56,16 +66,16 @@ mod test {
 
     #[test]
     fn ch07_hand_parse() {
-        let hand = Hand::parse(String::from("AAKK5 123"), false);
+        let hand = Hand::parse("AAKK5 123", false).unwrap();
         assert_eq!(
             hand,
             Hand {
-                cards: vec![
-                    Card::from('A'),
-                    Card::from('A'),
-                    Card::from('K'),
-                    Card::from('K'),
-                    Card::from('5')
+                cards: [
+                    Card::from('A').unwrap(),
+                    Card::from('A').unwrap(),
+                    Card::from('K').unwrap(),
+                    Card::from('K').unwrap(),
+                    Card::from('5').unwrap()
                 ],
                 hand_type: HandType::TwoPair,
                 bid: 123
@@ -76,32 +86,57 @@ mod test {
     #[test]
     fn ch07_hand_ord() {
         let mut hands = vec![
-            Hand::parse(String::from("AAKK5 123"), false),
-            Hand::parse(String::from("TTTT3 123"), false),
-            Hand::parse(String::from("KKJJ4 123"), false),
-            Hand::parse(String::from("KK234 123"), false),
-            Hand::parse(String::from("2AA34 123"), false),
+            Hand::parse("AAKK5 123", false).unwrap(),
+            Hand::parse("TTTT3 123", false).unwrap(),
+            Hand::parse("KKJJ4 123", false).unwrap(),
+            Hand::parse("KK234 123", false).unwrap(),
+            Hand::parse("2AA34 123", false).unwrap(),
         ];
 
         hands.sort();
 
         let expected = vec![
-            Hand::parse(String::from("2AA34 123"), false),
-            Hand::parse(String::from("KK234 123"), false),
-            Hand::parse(String::from("KKJJ4 123"), false),
-            Hand::parse(String::from("AAKK5 123"), false),
-            Hand::parse(String::from("TTTT3 123"), false),
+            Hand::parse("2AA34 123", false).unwrap(),
+            Hand::parse("KK234 123", false).unwrap(),
+            Hand::parse("KKJJ4 123", false).unwrap(),
+            Hand::parse("AAKK5 123", false).unwrap(),
+            Hand::parse("TTTT3 123", false).unwrap(),
         ];
 
         assert_eq!(hands, expected);
 
         assert!(
-            Hand::parse(String::from("KKQQQ 123"), false)
-                > Hand::parse(String::from("QQJJJ 123"), false)
+            Hand::parse("KKQQQ 123", false).unwrap() > Hand::parse("QQJJJ 123", false).unwrap()
         );
         assert!(
-            Hand::parse(String::from("QQJJJ 123"), false)
-                > Hand::parse(String::from("KKKJ5 123"), false)
+            Hand::parse("QQJJJ 123", false).unwrap() > Hand::parse("KKKJ5 123", false).unwrap()
+        );
+    }
+
+    #[test]
+    fn ch07_hand_parse_invalid_card() {
+        let error = Hand::parse("AAXK5 123", false).unwrap_err();
+        assert_eq!(
+            error,
+            ChallengeError::parse("X", 3, "Attempted to parse invalid card.")
+        );
+    }
+
+    #[test]
+    fn ch07_hand_parse_wrong_size() {
+        let error = Hand::parse("AAK 123", true).unwrap_err();
+        assert_eq!(
+            error,
+            ChallengeError::parse("AAK", 1, "Expected a hand of five cards.")
+        );
+    }
+
+    #[test]
+    fn ch07_hand_parse_bad_bid() {
+        let error = Hand::parse("AAKK5 12e", false).unwrap_err();
+        assert_eq!(
+            error,
+            ChallengeError::parse("12e", 7, "Could not convert bid to number.")
         );
     }
 }
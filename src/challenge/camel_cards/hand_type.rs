@@ -14,45 +14,27 @@ pub enum HandType {
 }
 
 impl HandType {
-    pub fn parse(hand: &Vec<Card>) -> Self {
+    // Jokers always join the largest group of matching cards, so only the
+    // two largest groups decide the type.
+    pub fn parse(hand: &[Card; 5]) -> Self {
         let mut counts: HashMap<&Card, u8> = HashMap::new();
-        for card in hand {
+        for card in hand.iter().filter(|card| **card != Card::CJoker) {
             *counts.entry(card).or_insert(0) += 1;
         }
-
-        let joker_count = counts.get(&Card::CJoker).unwrap_or(&0);
-
-        match (counts.len(), joker_count) {
-            (5, 0) => HandType::HighCard,
-            (5, 1) => HandType::OnePair,
-            (4, 0) => HandType::OnePair,
-            (4, _) => HandType::ThreeOfAKind,
-            (3, 0) => {
-                if counts.iter().any(|(_, v)| *v == 3) {
-                    return HandType::ThreeOfAKind;
-                }
-                HandType::TwoPair
-            }
-            (3, 1) => {
-                if counts.iter().any(|(_, v)| *v == 3) {
-                    return HandType::FourOfAKind;
-                }
-                HandType::FullHouse
-            }
-            (3, _) => HandType::FourOfAKind,
-            (2, 0) => {
-                if counts.iter().any(|(_, v)| *v == 4) {
-                    return HandType::FourOfAKind;
-                }
-                HandType::FullHouse
-            }
-            (2, _) => HandType::FiveOfAKind,
-            (1, _) => HandType::FiveOfAKind,
-            (_, _) => panic!(
-                "Invalid hand type. Hand was {}, with {} jokers.",
-                hand.iter().map(|card| card.to_string()).collect::<String>(),
-                joker_count
-            ),
+        let joker_count = 5 - counts.values().sum::<u8>();
+        let mut groups: Vec<u8> = counts.into_values().collect();
+        groups.sort_unstable_by(|a, b| b.cmp(a));
+        let largest = groups.first().unwrap_or(&0) + joker_count;
+        let second = *groups.get(1).unwrap_or(&0);
+
+        match (largest, second) {
+            (5, _) => HandType::FiveOfAKind,
+            (4, _) => HandType::FourOfAKind,
+            (3, 2) => HandType::FullHouse,
+            (3, _) => HandType::ThreeOfAKind,
+            (2, 2) => HandType::TwoPair,
+            (2, _) => HandType::OnePair,
+            _ => HandType::HighCard,
         }
     }
 }
@@ -63,14 +45,17 @@ mod test {
 
     use super::*;
 
-    fn get_cards(hand: &str) -> Vec<Card> {
-        hand.chars().map(|c| Card::from(c)).collect::<Vec<Card>>()
+    fn get_cards(hand: &str) -> [Card; 5] {
+        let cards: Vec<Card> = hand.chars().map(|c| Card::from(c).unwrap()).collect();
+        cards.try_into().unwrap()
     }
 
-    fn get_cards_joker(hand: &str) -> Vec<Card> {
-        hand.chars()
-            .map(|c| Card::from_with_joker(c))
-            .collect::<Vec<Card>>()
+    fn get_cards_joker(hand: &str) -> [Card; 5] {
+        let cards: Vec<Card> = hand
+            .chars()
+            .map(|c| Card::from_with_joker(c).unwrap())
+            .collect();
+        cards.try_into().unwrap()
     }
 
     #[test]
@@ -125,5 +110,8 @@ mod test {
 
         let hand = get_cards_joker("J5335");
         assert_eq!(HandType::parse(&hand), HandType::FullHouse);
+
+        let hand = get_cards_joker("JJJJJ");
+        assert_eq!(HandType::parse(&hand), HandType::FiveOfAKind);
     }
 }
@@ -1,31 +1,35 @@
 use num::BigInt;
 
-use crate::utils::to_lines_vec;
+use crate::{answer::Answer, context::SolveContext, error::ChallengeError, utils::to_lines_vec};
 
-use self::hand::Hand;
+pub use self::{card::Card, hand::Hand, hand_type::HandType};
 
-use super::Challenge;
-use std::fs::File;
+use super::{Challenge, Registration};
+use std::io::BufRead;
 
 mod card;
 mod hand;
 mod hand_type;
 
+pub const REGISTRATION: Registration = Registration {
+    year: 2023,
+    day: 7,
+    title: "Camel Cards",
+    version: 1,
+    create: || Box::new(CamelCards::default()),
+};
+
 #[derive(Default)]
 pub struct CamelCards {
     lines: Vec<String>,
 }
 
 impl Challenge for CamelCards {
-    fn load(&mut self, file: &File) {
-        self.lines = to_lines_vec(file);
+    fn load(&mut self, reader: &mut dyn BufRead) -> Result<(), ChallengeError> {
+        self.load_lines(to_lines_vec(reader)?)
     }
-    fn solve_part_one(&self) -> String {
-        let mut hands: Vec<Hand> = self
-            .lines
-            .iter()
-            .map(|line| Hand::parse(line.clone(), false))
-            .collect();
+    fn solve_part_one(&self, _context: &SolveContext) -> Result<Answer, ChallengeError> {
+        let mut hands = self.parse_hands(false)?;
         hands.sort();
         let result: BigInt = hands
             .iter()
@@ -35,14 +39,10 @@ impl Challenge for CamelCards {
                 acc + BigInt::from(winnings)
             });
 
-        format!("{}", result)
+        Ok(Answer::from(result))
     }
-    fn solve_part_two(&self) -> String {
-        let mut hands: Vec<Hand> = self
-            .lines
-            .iter()
-            .map(|line| Hand::parse(line.clone(), true))
-            .collect();
+    fn solve_part_two(&self, _context: &SolveContext) -> Result<Answer, ChallengeError> {
+        let mut hands = self.parse_hands(true)?;
         hands.sort();
         let result: BigInt = hands
             .iter()
@@ -52,7 +52,45 @@ impl Challenge for CamelCards {
                 acc + BigInt::from(winnings)
             });
 
-        format!("{}", result)
+        Ok(Answer::from(result))
+    }
+    fn queries(&self) -> Vec<&'static str> {
+        vec!["hand <cards>"]
+    }
+    fn query(&self, command: &str, args: &[&str]) -> Result<String, ChallengeError> {
+        match (command, args) {
+            ("hand", [cards]) => {
+                // The bid does not affect the hand type.
+                let line = format!("{} 0", cards);
+                Ok(format!(
+                    "{}: {:?} ({:?} with jokers)",
+                    cards,
+                    Hand::parse(&line, false)?.hand_type(),
+                    Hand::parse(&line, true)?.hand_type()
+                ))
+            }
+            ("hand", _) => Err(ChallengeError::solve("Usage: hand <cards>")),
+            _ => Err(ChallengeError::unknown_command(command, &self.queries())),
+        }
+    }
+    fn parse_line(&self, line: &str) -> Option<Result<String, ChallengeError>> {
+        Some(Hand::parse(line, false).map(|hand| format!("{:?}", hand)))
+    }
+}
+
+impl CamelCards {
+    fn load_lines(&mut self, lines: Vec<String>) -> Result<(), ChallengeError> {
+        self.lines = lines;
+        self.parse_hands(false)?;
+        Ok(())
+    }
+
+    fn parse_hands(&self, with_joker: bool) -> Result<Vec<Hand>, ChallengeError> {
+        self.lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| Hand::parse(line, with_joker).map_err(|e| e.at_line(i + 1)))
+            .collect()
     }
 }
 
@@ -60,6 +98,18 @@ impl Challenge for CamelCards {
 mod test {
     use super::*;
 
+    #[test]
+    fn ch07_camel_cards_hand_query() {
+        let camel_cards = CamelCards::default();
+
+        assert_eq!(
+            camel_cards.query("hand", &["KTJJT"]).unwrap(),
+            "KTJJT: TwoPair (FourOfAKind with jokers)"
+        );
+        assert!(camel_cards.query("hand", &["KTJJ"]).is_err());
+        assert!(camel_cards.query("bid", &[]).is_err());
+    }
+
     fn get_input() -> Vec<String> {
         vec![
             String::from("32T3K 765"),
@@ -73,16 +123,29 @@ mod test {
     #[test]
     fn ch07_camel_cards_part_one() {
         let mut camel_cards = CamelCards::default();
-        camel_cards.lines = get_input();
+        camel_cards.load_str(&get_input().join("\n")).unwrap();
 
-        assert_eq!(camel_cards.solve_part_one(), "6440");
+        assert_eq!(camel_cards.solve_part_one(&SolveContext::default()).unwrap(), "6440");
     }
 
     #[test]
     fn ch07_camel_cards_part_two() {
         let mut camel_cards = CamelCards::default();
-        camel_cards.lines = get_input();
+        camel_cards.load_str(&get_input().join("\n")).unwrap();
 
-        assert_eq!(camel_cards.solve_part_two(), "5905");
+        assert_eq!(camel_cards.solve_part_two(&SolveContext::default()).unwrap(), "5905");
+    }
+
+    #[test]
+    fn ch07_camel_cards_load_reports_line() {
+        let mut lines = get_input();
+        lines[3] = String::from("KTJJT");
+
+        let mut camel_cards = CamelCards::default();
+        let error = camel_cards.load_str(&lines.join("\n")).unwrap_err();
+        assert_eq!(
+            error,
+            ChallengeError::parse("KTJJT", 1, "Could not find bid string.").at_line(4)
+        );
     }
 }
@@ -1,4 +1,5 @@
 use super::cube_subset::CubeSubset;
+use crate::{error::ChallengeError, utils::split_offsets};
 use std::cmp::max;
 
 #[derive(Debug, PartialEq)]
@@ -8,10 +9,10 @@ pub struct CubeGame {
 }
 
 impl CubeGame {
-    pub fn new(line: &str) -> Self {
-        let id = CubeGame::extract_game_id(line);
-        let subsets = CubeGame::extract_game_segments(line);
-        CubeGame { subsets, id }
+    pub fn new(line: &str) -> Result<Self, ChallengeError> {
+        let id = CubeGame::extract_game_id(line)?;
+        let subsets = CubeGame::extract_game_segments(line)?;
+        Ok(CubeGame { subsets, id })
     }
 
     pub fn is_possible(&self, max_red: u32, max_green: u32, max_blue: u32) -> bool {
@@ -20,17 +21,15 @@ impl CubeGame {
                 return false;
             }
         }
-        return true;
+        true
     }
 
     pub fn get_power(&self) -> u32 {
         let (red, green, blue) = self.min_required();
-        let power = red * green * blue;
-
-        power
+        red * green * blue
     }
 
-    fn min_required(&self) -> (u32, u32, u32) {
+    pub fn min_required(&self) -> (u32, u32, u32) {
         let mut red = 0;
         let mut green = 0;
         let mut blue = 0;
@@ -42,49 +41,28 @@ impl CubeGame {
         (red, green, blue)
     }
 
-    fn extract_game_id(line: &str) -> u32 {
-        let game_id_segment = line.split(":").next().expect(
-            format!(
-                "Could not identify string segment before ':' in line: {}",
-                line
-            )
-            .as_str(),
-        );
-        game_id_segment
-            .split(" ")
+    fn extract_game_id(line: &str) -> Result<u32, ChallengeError> {
+        let game_id_segment = line.split(":").next().unwrap_or(line);
+        let (offset, game_id) = split_offsets(game_id_segment, ' ')
             .last()
-            .expect(
-                format!(
-                    "Could not identify value after ' ' in game id segment: {}",
-                    game_id_segment
-                )
-                .as_str(),
-            )
+            .unwrap_or((0, game_id_segment));
+        game_id
             .parse::<u32>()
-            .expect(
-                format!(
-                    "Could not parse u32 game ID value from line segment {}",
-                    game_id_segment
-                )
-                .as_str(),
-            )
-    }
-
-    fn extract_game_segments(line: &str) -> Vec<CubeSubset> {
-        let game_segments = line.split(":").skip(1).next().expect(
-            format!(
-                "Could not identify string segment after ':' in line: {}",
-                line
-            )
-            .as_str(),
-        );
-        let game_segments = game_segments.split(";").collect::<Vec<&str>>();
+            .map_err(|_| ChallengeError::parse(game_id, offset + 1, "Could not parse game ID"))
+    }
+
+    fn extract_game_segments(line: &str) -> Result<Vec<CubeSubset>, ChallengeError> {
+        let (segments_offset, game_segments) =
+            split_offsets(line, ':').nth(1).ok_or_else(|| {
+                ChallengeError::parse(line, 1, "Could not identify game segments after ':'")
+            })?;
         let mut subsets = Vec::new();
-        for segment in game_segments {
-            let subset = CubeSubset::parse(segment);
+        for (offset, segment) in split_offsets(game_segments, ';') {
+            let subset = CubeSubset::parse(segment)
+                .map_err(|e| e.offset_columns(segments_offset + offset))?;
             subsets.push(subset);
         }
-        subsets
+        Ok(subsets)
     }
 }
 
@@ -95,33 +73,33 @@ mod test {
     #[test]
     fn ch02_cubegame_extract_game_id_single_digit() {
         let line = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
-        let game_id = CubeGame::extract_game_id(line);
+        let game_id = CubeGame::extract_game_id(line).unwrap();
         assert_eq!(game_id, 1);
     }
 
     #[test]
     fn ch02_cubegame_extract_game_id_double_digit() {
         let line = "Game 93: 1 blue, 6 red; 2 blue, 10 red, 2 green; 2 green, 2 blue; 4 red, 2 blue; 4 red, 3 green";
-        let game_id = CubeGame::extract_game_id(line);
+        let game_id = CubeGame::extract_game_id(line).unwrap();
         assert_eq!(game_id, 93);
     }
 
     #[test]
     fn ch02_cubegame_extract_game_id_triple_digit() {
         let line = "Game 100: 1 blue, 6 red; 2 blue, 10 red, 2 green; 2 green, 2 blue; 4 red, 2 blue; 4 red, 3 green";
-        let game_id = CubeGame::extract_game_id(line);
+        let game_id = CubeGame::extract_game_id(line).unwrap();
         assert_eq!(game_id, 100);
     }
 
     #[test]
     fn ch02_cubegame_extract_game_segments() {
         let line = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
-        let subsets = CubeGame::extract_game_segments(line);
+        let subsets = CubeGame::extract_game_segments(line).unwrap();
         assert_eq!(subsets.len(), 3);
         let expected_subsets = vec![
-            CubeSubset::parse("3 blue, 4 red"),
-            CubeSubset::parse("1 red, 2 green, 6 blue"),
-            CubeSubset::parse("2 green"),
+            CubeSubset::parse("3 blue, 4 red").unwrap(),
+            CubeSubset::parse("1 red, 2 green, 6 blue").unwrap(),
+            CubeSubset::parse("2 green").unwrap(),
         ];
 
         assert_eq!(subsets, expected_subsets);
@@ -130,14 +108,14 @@ mod test {
     #[test]
     fn ch02_cubegame_parse() {
         let line = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
-        let game = CubeGame::new(line);
+        let game = CubeGame::new(line).unwrap();
         assert_eq!(game.id, 1);
         assert_eq!(game.subsets.len(), 3);
 
         let expected = vec![
-            CubeSubset::parse("3 blue, 4 red"),
-            CubeSubset::parse("1 red, 2 green, 6 blue"),
-            CubeSubset::parse("2 green"),
+            CubeSubset::parse("3 blue, 4 red").unwrap(),
+            CubeSubset::parse("1 red, 2 green, 6 blue").unwrap(),
+            CubeSubset::parse("2 green").unwrap(),
         ];
 
         assert_eq!(game.subsets, expected);
@@ -146,7 +124,7 @@ mod test {
     #[test]
     fn ch02_cubegame_min_required() {
         let line = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
-        let game = CubeGame::new(line);
+        let game = CubeGame::new(line).unwrap();
         let (red, green, blue) = game.min_required();
         assert_eq!(red, 4);
         assert_eq!(green, 2);
@@ -156,7 +134,7 @@ mod test {
     #[test]
     fn ch02_cubegame_get_power() {
         let line = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
-        let game = CubeGame::new(line);
+        let game = CubeGame::new(line).unwrap();
         let power = game.get_power();
         assert_eq!(power, 48);
     }
@@ -164,8 +142,25 @@ mod test {
     #[test]
     fn ch02_cubegame_get_power_2() {
         let line = "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red";
-        let game = CubeGame::new(line);
+        let game = CubeGame::new(line).unwrap();
         let power = game.get_power();
         assert_eq!(power, 1560);
     }
+
+    #[test]
+    fn ch02_cubegame_parse_reports_column() {
+        let line = "Game 4: 1 green, 3 red; 3 grey";
+        let error = CubeGame::new(line).unwrap_err();
+        assert_eq!(error, ChallengeError::parse("grey", 27, "Unknown color"));
+    }
+
+    #[test]
+    fn ch02_cubegame_parse_bad_id() {
+        let line = "Game x: 1 green";
+        let error = CubeGame::new(line).unwrap_err();
+        assert_eq!(
+            error,
+            ChallengeError::parse("x", 6, "Could not parse game ID")
+        );
+    }
 }
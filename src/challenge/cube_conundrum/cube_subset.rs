@@ -1,3 +1,5 @@
+use crate::{error::ChallengeError, utils::split_offsets};
+
 #[derive(Debug, PartialEq)]
 
 pub struct CubeSubset {
@@ -7,32 +9,37 @@ pub struct CubeSubset {
 }
 
 impl CubeSubset {
-    pub fn parse(segment: &str) -> Self {
-        let colors = segment.split(",").collect::<Vec<&str>>();
+    pub fn parse(segment: &str) -> Result<Self, ChallengeError> {
         let mut red = 0;
         let mut green = 0;
         let mut blue = 0;
         // " 3 blue, 4 red"
-        for color in colors {
+        for (offset, color) in split_offsets(segment, ',') {
+            let column = offset + color.len() - color.trim_start().len() + 1;
             let color = color.trim();
-            let mut color_iter = color.split(" ");
-            let count = color_iter
-                .next()
-                .expect(format!("Could not identify count in color: {}", color).as_str())
-                .parse::<u32>()
-                .expect(format!("Could not parse u8 count in color: {}", color).as_str());
-            let color = color_iter
-                .next()
-                .expect(format!("Could not identify color in color: {}", color).as_str());
-            match color {
-                "red" => red = count as u32,
-                "green" => green = count as u32,
-                "blue" => blue = count as u32,
-                _ => panic!("Unknown color: {}", color),
+            let mut color_iter = split_offsets(color, ' ');
+            let (_, count_str) = color_iter.next().unwrap_or((0, color));
+            let count = count_str.parse::<u32>().map_err(|_| {
+                ChallengeError::parse(count_str, column, "Could not parse cube count")
+            })?;
+            let (color_offset, color_str) = color_iter.next().ok_or_else(|| {
+                ChallengeError::parse(color, column, "Could not identify color")
+            })?;
+            match color_str {
+                "red" => red = count,
+                "green" => green = count,
+                "blue" => blue = count,
+                _ => {
+                    return Err(ChallengeError::parse(
+                        color_str,
+                        column + color_offset,
+                        "Unknown color",
+                    ))
+                }
             }
         }
 
-        CubeSubset { red, green, blue }
+        Ok(CubeSubset { red, green, blue })
     }
 }
 
@@ -43,7 +50,7 @@ mod test {
     #[test]
     fn ch02_cubesubset_parse() {
         let segment = " 3 blue, 4 red";
-        let subset = CubeSubset::parse(segment);
+        let subset = CubeSubset::parse(segment).unwrap();
         assert_eq!(subset.red, 4);
         assert_eq!(subset.green, 0);
         assert_eq!(subset.blue, 3);
@@ -52,7 +59,7 @@ mod test {
     #[test]
     fn ch02_cubesubset_parse_multiple_colors() {
         let segment = " 3 blue, 4 red, 2 green";
-        let subset = CubeSubset::parse(segment);
+        let subset = CubeSubset::parse(segment).unwrap();
         assert_eq!(subset.red, 4);
         assert_eq!(subset.green, 2);
         assert_eq!(subset.blue, 3);
@@ -61,7 +68,7 @@ mod test {
     #[test]
     fn ch02_cubesubset_parse_multiple_colors_unordered() {
         let segment = " 3 blue, 4 red, 2 green";
-        let subset = CubeSubset::parse(segment);
+        let subset = CubeSubset::parse(segment).unwrap();
         assert_eq!(subset.red, 4);
         assert_eq!(subset.green, 2);
         assert_eq!(subset.blue, 3);
@@ -70,9 +77,26 @@ mod test {
     #[test]
     fn ch02_cubesubset_parse_one_color() {
         let segment = " 3 blue";
-        let subset = CubeSubset::parse(segment);
+        let subset = CubeSubset::parse(segment).unwrap();
         assert_eq!(subset.red, 0);
         assert_eq!(subset.green, 0);
         assert_eq!(subset.blue, 3);
     }
+
+    #[test]
+    fn ch02_cubesubset_parse_unknown_color() {
+        let segment = " 3 blue, 4 purple";
+        let error = CubeSubset::parse(segment).unwrap_err();
+        assert_eq!(error, ChallengeError::parse("purple", 12, "Unknown color"));
+    }
+
+    #[test]
+    fn ch02_cubesubset_parse_bad_count() {
+        let segment = " 3 blue, x4 red";
+        let error = CubeSubset::parse(segment).unwrap_err();
+        assert_eq!(
+            error,
+            ChallengeError::parse("x4", 10, "Could not parse cube count")
+        );
+    }
 }
@@ -1,28 +1,47 @@
-use crate::utils::to_lines_vec;
+use crate::{answer::Answer, context::SolveContext, error::ChallengeError, utils::to_lines_vec};
 
-use self::cube_game::CubeGame;
+pub use self::{cube_game::CubeGame, cube_subset::CubeSubset};
 
-use super::Challenge;
-use std::fs::File;
+use super::{Challenge, Registration};
+use std::io::BufRead;
 
 mod cube_game;
 mod cube_subset;
 
+pub const REGISTRATION: Registration = Registration {
+    year: 2023,
+    day: 2,
+    title: "Cube Conundrum",
+    version: 1,
+    create: || Box::new(CubeConundrum::default()),
+};
+
 #[derive(Default)]
 pub struct CubeConundrum {
     games: Vec<CubeGame>,
 }
 
 impl Challenge for CubeConundrum {
-    fn load(&mut self, file: &File) {
-        let lines: Vec<String> = to_lines_vec(file);
-        self.load_lines(lines);
+    fn load(&mut self, reader: &mut dyn BufRead) -> Result<(), ChallengeError> {
+        let lines: Vec<String> = to_lines_vec(reader)?;
+        self.load_lines(lines)
     }
-    fn solve_part_one(&self) -> String {
+    fn solve_part_one(&self, context: &SolveContext) -> Result<Answer, ChallengeError> {
         let max_red = 12;
         let max_green = 13;
         let max_blue = 14;
 
+        if context.explain().enabled() {
+            for game in &self.games {
+                let possible = game.is_possible(max_red, max_green, max_blue);
+                CubeConundrum::explain_game(
+                    context,
+                    game,
+                    if possible { "possible" } else { "impossible" },
+                );
+            }
+        }
+
         let possible_games_sum = self
             .games
             .iter()
@@ -30,41 +49,57 @@ impl Challenge for CubeConundrum {
             .map(|game| game.id)
             .sum::<u32>();
 
-        format!("{}", possible_games_sum)
+        Ok(Answer::from(possible_games_sum))
     }
-    fn solve_part_two(&self) -> String {
+    fn solve_part_two(&self, context: &SolveContext) -> Result<Answer, ChallengeError> {
+        if context.explain().enabled() {
+            for game in &self.games {
+                CubeConundrum::explain_game(context, game, &format!("power {}", game.get_power()));
+            }
+        }
+
         let sum_of_powers = self.games.iter().map(|game| game.get_power()).sum::<u32>();
 
-        format!("{}", sum_of_powers)
+        Ok(Answer::from(sum_of_powers))
+    }
+    fn parse_line(&self, line: &str) -> Option<Result<String, ChallengeError>> {
+        Some(CubeGame::new(line).map(|game| format!("{:?}", game)))
     }
 }
 
 impl CubeConundrum {
-    fn load_lines(&mut self, lines: Vec<String>) {
+    fn load_lines(&mut self, lines: Vec<String>) -> Result<(), ChallengeError> {
         let mut games: Vec<CubeGame> = Vec::new();
-        lines.into_iter().for_each(|line| {
-            games.push(CubeGame::new(&line));
-        });
+        for (i, line) in lines.into_iter().enumerate() {
+            games.push(CubeGame::new(&line).map_err(|e| e.at_line(i + 1))?);
+        }
 
         self.games = games;
+        Ok(())
+    }
+
+    fn explain_game(context: &SolveContext, game: &CubeGame, outcome: &str) {
+        let (red, green, blue) = game.min_required();
+        context.explain().step(format_args!(
+            "Game {}: at least {} red, {} green, {} blue -> {}",
+            game.id, red, green, blue, outcome
+        ));
     }
 }
 
 #[cfg(test)]
 mod test {
+    use std::rc::Rc;
+
     use super::*;
 
-    fn get_mock_input() -> Vec<String> {
+    fn get_mock_input() -> Vec<&'static str> {
         vec![
-            String::from("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"),
-            String::from("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue"),
-            String::from(
-                "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
-            ),
-            String::from(
-                "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
-            ),
-            String::from("Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"),
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
         ]
     }
 
@@ -73,9 +108,9 @@ mod test {
         let test_lines = get_mock_input();
 
         let mut cube_conundrum = CubeConundrum::default();
-        cube_conundrum.load_lines(test_lines);
+        cube_conundrum.load_str(&test_lines.join("\n")).unwrap();
 
-        assert_eq!(cube_conundrum.solve_part_one(), "8");
+        assert_eq!(cube_conundrum.solve_part_one(&SolveContext::default()).unwrap(), "8");
     }
 
     #[test]
@@ -83,8 +118,39 @@ mod test {
         let test_lines = get_mock_input();
 
         let mut cube_conundrum = CubeConundrum::default();
-        cube_conundrum.load_lines(test_lines);
+        cube_conundrum.load_str(&test_lines.join("\n")).unwrap();
+
+        assert_eq!(cube_conundrum.solve_part_two(&SolveContext::default()).unwrap(), "2286");
+    }
+
+    #[test]
+    fn ch02_cubeconundrum_part_two_explains_minimum_cubes() {
+        let mut cube_conundrum = CubeConundrum::default();
+        cube_conundrum
+            .load_str(&get_mock_input()[..2].join("\n"))
+            .unwrap();
+        let (explain, captured) = crate::explain::test::captured();
+        let context = SolveContext::default().with_explain(Rc::new(explain));
+
+        cube_conundrum.solve_part_two(&context).unwrap();
+        assert_eq!(
+            captured.contents(),
+            "  Game 1: at least 4 red, 2 green, 6 blue -> power 48\n  \
+             Game 2: at least 1 red, 3 green, 4 blue -> power 12\n"
+        );
+    }
+
+    #[test]
+    fn ch02_cubeconundrum_load_reports_line() {
+        let mut test_lines = get_mock_input();
+        test_lines[2] = "Game 3: 8 green; 5 blue, 4 rde";
+
+        let mut cube_conundrum = CubeConundrum::default();
+        let error = cube_conundrum.load_str(&test_lines.join("\n")).unwrap_err();
 
-        assert_eq!(cube_conundrum.solve_part_two(), "2286");
+        assert_eq!(
+            error,
+            ChallengeError::parse("rde", 28, "Unknown color").at_line(3)
+        );
     }
 }
@@ -1,33 +1,32 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
-use super::{adjacent::Adjacent, gear::Gear, part_number::PartNumber};
+use crate::{error::ChallengeError, utils::Grid};
+
+use super::{gear::Gear, part_number::PartNumber};
 
 #[derive(Default)]
 pub struct EngineSchematic {
-    schematic: Vec<Vec<char>>,
+    schematic: Grid<char>,
     part_numbers: Vec<PartNumber>,
     gears: HashMap<(usize, usize), Gear>,
 }
 
 impl EngineSchematic {
-    pub fn new(lines: Vec<String>) -> Self {
-        let mut schematic: Vec<Vec<char>> = Vec::new();
-        lines.into_iter().for_each(|line| {
-            let chars = line.chars().collect();
-            schematic.push(chars);
-        });
+    pub fn new(lines: Vec<String>) -> Result<Self, ChallengeError> {
+        let schematic = Grid::parse(&lines, |c, _| Ok(c))?;
 
         let mut part_numbers = Vec::new();
         let mut gears = HashMap::new();
 
-        for (row_index, row) in schematic.iter().enumerate() {
+        for (row_index, row) in schematic.rows().enumerate() {
             let mut col_index = 0;
             while col_index < row.len() {
-                if row.get(col_index).unwrap().eq(&'*') {
+                if row[col_index] == '*' {
                     let gear = Gear::new();
                     gears.insert((row_index, col_index), gear);
                 }
-                if let Ok(part_number) = PartNumber::new(row_index, &row, col_index) {
+                if row[col_index].is_ascii_digit() {
+                    let part_number = PartNumber::new(row_index, row, col_index)?;
                     part_numbers.push(part_number);
                     col_index += part_number.length;
                     continue;
@@ -44,7 +43,7 @@ impl EngineSchematic {
 
         schematic.connect_all_gears();
 
-        schematic
+        Ok(schematic)
     }
 
     pub fn get_nums_adjacent_to_symbols(&self) -> Vec<PartNumber> {
@@ -57,9 +56,21 @@ impl EngineSchematic {
                         return true;
                     }
                 }
-                return false;
+                false
             })
-            .map(|p| p.clone())
+            .copied()
+            .collect()
+    }
+
+    // Every symbol touching the part number, ordered by position.
+    pub fn symbols_adjacent_to(&self, part_number: &PartNumber) -> Vec<(char, usize, usize)> {
+        (part_number.col..(part_number.col + part_number.length))
+            .flat_map(|i| self.schematic.neighbors8((part_number.row, i)))
+            .filter(|(_, ch)| EngineSchematic::is_symbol(**ch))
+            .map(|((r, c), ch)| (r, c, *ch))
+            .collect::<BTreeSet<(usize, usize, char)>>()
+            .into_iter()
+            .map(|(r, c, ch)| (ch, r, c))
             .collect()
     }
 
@@ -79,16 +90,18 @@ impl EngineSchematic {
     }
 
     fn symbol_adjacent(&self, row: usize, col: usize) -> bool {
-        Adjacent::new(&self.schematic, (row, col)).any(|(ch, _, _)| EngineSchematic::is_symbol(ch))
+        self.schematic
+            .neighbors8((row, col))
+            .any(|(_, ch)| EngineSchematic::is_symbol(*ch))
     }
 
     fn connect_gears(&mut self, part_number: &PartNumber) {
         for i in part_number.col..(part_number.col + part_number.length) {
-            Adjacent::new(&self.schematic, (part_number.row, i)).for_each(|(ch, r, c)| {
-                if ch.eq(&'*') {
-                    let gear: &mut Gear =
-                        self.gears.get_mut(&(r, c)).expect("Could not find gear.");
-                    gear.add_part(&part_number);
+            self.schematic
+                .neighbors8((part_number.row, i))
+                .for_each(|((r, c), ch)| {
+                if let Some(gear) = self.gears.get_mut(&(r, c)).filter(|_| ch.eq(&'*')) {
+                    gear.add_part(part_number);
                 }
             });
         }
@@ -99,10 +112,7 @@ impl EngineSchematic {
             return false;
         }
 
-        match val.to_digit(10) {
-            Some(_) => false,
-            None => true,
-        }
+        val.to_digit(10).is_none()
     }
 }
 
@@ -116,9 +126,8 @@ mod test {
             String::from("12..$"),
             String::from("..+.."),
         ];
-        let engine_schematic = EngineSchematic::new(input);
 
-        engine_schematic
+        EngineSchematic::new(input).unwrap()
     }
 
     #[test]
@@ -131,7 +140,7 @@ mod test {
             vec!['.', '.', '+', '.', '.'],
         ];
 
-        assert_eq!(expected, engine_schematic.schematic);
+        assert_eq!(Grid::new(expected).unwrap(), engine_schematic.schematic);
     }
 
     #[test]
@@ -190,7 +199,7 @@ mod test {
             String::from("12..."),
             String::from("..+.."),
         ];
-        let schematic = EngineSchematic::new(input);
+        let schematic = EngineSchematic::new(input).unwrap();
 
         let expected = vec![PartNumber {
             row: 1,
@@ -202,6 +211,20 @@ mod test {
         assert_eq!(expected, schematic.get_nums_adjacent_to_symbols());
     }
 
+    #[test]
+    fn ch03_engine_schematic_symbols_adjacent_to() {
+        let schematic = get_test_schematic();
+
+        assert_eq!(
+            schematic.symbols_adjacent_to(&schematic.part_numbers[1]),
+            vec![('+', 2, 2)]
+        );
+        assert_eq!(
+            schematic.symbols_adjacent_to(&schematic.part_numbers[0]),
+            vec![('$', 1, 4)]
+        );
+    }
+
     #[test]
     fn ch03_engine_schematic_connect_gears() {
         let input = vec![
@@ -209,7 +232,7 @@ mod test {
             String::from("10*.."),
             String::from("....."),
         ];
-        let mut schematic = EngineSchematic::new(input);
+        let mut schematic = EngineSchematic::new(input).unwrap();
         for part in schematic.part_numbers.clone() {
             schematic.connect_gears(&part);
         }
@@ -226,7 +249,7 @@ mod test {
             String::from("10*.."),
             String::from("...*2"),
         ];
-        let schematic = EngineSchematic::new(input);
+        let schematic = EngineSchematic::new(input).unwrap();
         let ratios = schematic.get_gear_ratios_adjacent_to_two_numbers();
         assert_eq!(ratios, vec![1230]);
     }
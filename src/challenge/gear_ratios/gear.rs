@@ -2,6 +2,7 @@ use std::collections::HashSet;
 
 use super::part_number::PartNumber;
 
+#[derive(Default)]
 pub struct Gear {
     part_numbers: HashSet<PartNumber>,
 }
@@ -15,7 +16,7 @@ impl Gear {
 
     pub fn add_part(&mut self, part_number: &PartNumber) {
         if !self.part_numbers.contains(part_number) {
-            self.part_numbers.insert(part_number.clone());
+            self.part_numbers.insert(*part_number);
         }
     }
 
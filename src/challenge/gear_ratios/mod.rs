@@ -1,79 +1,132 @@
-use crate::utils::to_lines_vec;
+use crate::{answer::Answer, context::SolveContext, error::ChallengeError, utils::to_lines_vec};
 
-use self::engine_schematic::EngineSchematic;
+pub use self::{engine_schematic::EngineSchematic, gear::Gear, part_number::PartNumber};
 
-use super::Challenge;
-use std::fs::File;
+use super::{Challenge, Registration};
+use std::io::BufRead;
 
-mod adjacent;
 mod engine_schematic;
 mod gear;
 mod part_number;
 
+pub const REGISTRATION: Registration = Registration {
+    year: 2023,
+    day: 3,
+    title: "Gear Ratios",
+    version: 1,
+    create: || Box::new(GearRatios::default()),
+};
+
 #[derive(Default)]
 pub struct GearRatios {
     engine_schematic: EngineSchematic,
 }
 
 impl Challenge for GearRatios {
-    fn load(&mut self, file: &File) {
-        self.engine_schematic = EngineSchematic::new(to_lines_vec(file));
+    fn load(&mut self, reader: &mut dyn BufRead) -> Result<(), ChallengeError> {
+        self.engine_schematic = EngineSchematic::new(to_lines_vec(reader)?)?;
+        Ok(())
     }
-    fn solve_part_one(&self) -> String {
-        let total: u32 = self
-            .engine_schematic
-            .get_nums_adjacent_to_symbols()
+    fn solve_part_one(&self, context: &SolveContext) -> Result<Answer, ChallengeError> {
+        let part_numbers = self.engine_schematic.get_nums_adjacent_to_symbols();
+        if context.explain().enabled() {
+            for part_number in &part_numbers {
+                let symbols: Vec<String> = self
+                    .engine_schematic
+                    .symbols_adjacent_to(part_number)
+                    .iter()
+                    .map(|(ch, r, c)| format!("{} at line {}, column {}", ch, r + 1, c + 1))
+                    .collect();
+                context.explain().step(format_args!(
+                    "{} at line {}, column {} touches {}",
+                    part_number.value,
+                    part_number.row + 1,
+                    part_number.col + 1,
+                    symbols.join(" and ")
+                ));
+            }
+        }
+
+        let total: u32 = part_numbers
             .iter()
             .map(|part_number| part_number.value)
             .sum();
 
-        format!("{}", total)
+        Ok(Answer::from(total))
     }
-    fn solve_part_two(&self) -> String {
+    fn solve_part_two(&self, _context: &SolveContext) -> Result<Answer, ChallengeError> {
         let total: u32 = self
             .engine_schematic
             .get_gear_ratios_adjacent_to_two_numbers()
             .iter()
             .sum();
 
-        format!("{}", total)
+        Ok(Answer::from(total))
     }
 }
 
 #[cfg(test)]
 mod test {
+    use std::rc::Rc;
+
     use super::*;
 
-    fn get_test_input() -> Vec<String> {
-        vec![
-            String::from("467..114.."),
-            String::from("...*......"),
-            String::from("..35..633."),
-            String::from("......#..."),
-            String::from("617*......"),
-            String::from(".....+.58."),
-            String::from("..592....."),
-            String::from("......755."),
-            String::from("...$.*...."),
-            String::from(".664.598.."),
-        ]
+    fn get_test_input() -> GearRatios {
+        let input = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+        let mut gear_ratios = GearRatios::default();
+        gear_ratios.load_str(input).unwrap();
+        gear_ratios
     }
 
     #[test]
     fn ch03_part_one() {
-        let input = get_test_input();
-        let mut gear_ratios = GearRatios::default();
-        gear_ratios.engine_schematic = EngineSchematic::new(input);
+        let gear_ratios = get_test_input();
 
-        assert_eq!(gear_ratios.solve_part_one(), "4361");
+        assert_eq!(gear_ratios.solve_part_one(&SolveContext::default()).unwrap(), "4361");
     }
 
     #[test]
     fn ch03_part_two() {
-        let input = get_test_input();
+        let gear_ratios = get_test_input();
+
+        assert_eq!(gear_ratios.solve_part_two(&SolveContext::default()).unwrap(), "467835");
+    }
+
+    #[test]
+    fn ch03_part_one_explains_touched_symbols() {
+        let mut gear_ratios = GearRatios::default();
+        gear_ratios.load_str("467..114..\n...*......\n..35..633.").unwrap();
+        let (explain, captured) = crate::explain::test::captured();
+        let context = SolveContext::default().with_explain(Rc::new(explain));
+
+        assert_eq!(gear_ratios.solve_part_one(&context).unwrap(), "502");
+        assert_eq!(
+            captured.contents(),
+            "  467 at line 1, column 1 touches * at line 2, column 4\n  \
+             35 at line 3, column 3 touches * at line 2, column 4\n"
+        );
+    }
+
+    #[test]
+    fn ch03_load_reports_line() {
         let mut gear_ratios = GearRatios::default();
-        gear_ratios.engine_schematic = EngineSchematic::new(input);
+        let error = gear_ratios
+            .load_str("467..114......\n..99999999999*")
+            .unwrap_err();
 
-        assert_eq!(gear_ratios.solve_part_two(), "467835");
+        assert_eq!(
+            error,
+            ChallengeError::parse("99999999999", 3, "Part number is too large").at_line(2)
+        );
     }
 }
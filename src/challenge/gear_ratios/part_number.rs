@@ -1,3 +1,5 @@
+use crate::error::ChallengeError;
+
 #[derive(Clone, Copy, PartialEq, Debug, Eq, Hash)]
 pub struct PartNumber {
     pub row: usize,
@@ -7,18 +9,23 @@ pub struct PartNumber {
 }
 
 impl PartNumber {
-    pub fn new(row_id: usize, row: &Vec<char>, start: usize) -> Result<Self, ()> {
-        if !row[start].is_digit(10) {
-            return Err(());
-        }
+    pub fn new(row_id: usize, row: &[char], start: usize) -> Result<Self, ChallengeError> {
         let num_str: String = row
             .iter()
             .skip(start)
-            .take_while(|ch| ch.is_digit(10))
+            .take_while(|ch| ch.is_ascii_digit())
             .collect();
-        let value = num_str
-            .parse::<u32>()
-            .expect(format!("Attempted to parse non-numeric char sequence: {}", num_str).as_str());
+        if num_str.is_empty() {
+            let found = row.get(start).map(|ch| ch.to_string()).unwrap_or_default();
+            return Err(
+                ChallengeError::parse(&found, start + 1, "Expected a part number")
+                    .at_line(row_id + 1),
+            );
+        }
+        let value = num_str.parse::<u32>().map_err(|_| {
+            ChallengeError::parse(&num_str, start + 1, "Part number is too large")
+                .at_line(row_id + 1)
+        })?;
 
         Ok(PartNumber {
             row: row_id,
@@ -59,10 +66,23 @@ mod test {
         assert_eq!(expected, PartNumber::new(4, &row, 6).unwrap());
     }
 
+    #[test]
+    fn ch03_part_number_too_large() {
+        let row: Vec<char> = "..99999999999.".chars().collect();
+
+        assert_eq!(
+            Err(ChallengeError::parse("99999999999", 3, "Part number is too large").at_line(1)),
+            PartNumber::new(0, &row, 2)
+        );
+    }
+
     #[test]
     fn ch03_part_number_errs() {
         let row = vec!['.', '.', '4', '6', '1', '.', '7', '6', '.'];
 
-        assert_eq!(Err(()), PartNumber::new(4, &row, 1));
+        assert_eq!(
+            Err(ChallengeError::parse(".", 2, "Expected a part number").at_line(5)),
+            PartNumber::new(4, &row, 1)
+        );
     }
 }
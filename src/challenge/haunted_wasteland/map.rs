@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use super::node::Node;
+use crate::error::ChallengeError;
 
 #[derive(Default)]
 pub struct Map {
@@ -8,31 +9,40 @@ pub struct Map {
 }
 
 impl Map {
-    pub fn new(lines: Vec<String>) -> Self {
+    pub fn new(lines: Vec<String>) -> Result<Self, ChallengeError> {
         let map = lines
-            .into_iter()
-            .map(|line| {
-                let node = Node::new(line);
-                return (node.address.clone(), node);
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let node = Node::new(line).map_err(|e| e.at_line(i + 1))?;
+                Ok((node.address.clone(), node))
             })
-            .collect::<HashMap<String, Node>>();
+            .collect::<Result<HashMap<String, Node>, ChallengeError>>()?;
 
-        Map { map }
+        Ok(Map { map })
     }
 
-    pub fn iter(&self) -> std::collections::hash_map::Iter<String, Node> {
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn iter(&self) -> std::collections::hash_map::Iter<'_, String, Node> {
         self.map.iter()
     }
 
-    pub fn travel(&self, location: &str, direction: &char) -> &str {
-        match direction {
-            'L' | 'R' => self
-                .map
-                .get(location)
-                .expect(format!("Got lost at unknown location: {}", location).as_str())
-                .get_next(direction),
-            _ => panic!("Cannot travel in the {} direction.", direction),
-        }
+    pub fn travel(&self, location: &str, direction: &char) -> Result<&str, ChallengeError> {
+        let node = self.map.get(location).ok_or_else(|| {
+            ChallengeError::solve(&format!("Got lost at unknown location: {}", location))
+        })?;
+        let next = node.get_next(direction).ok_or_else(|| {
+            ChallengeError::solve(&format!("Cannot travel in the {} direction.", direction))
+        })?;
+
+        Ok(next)
     }
 }
 
@@ -46,10 +56,10 @@ mod test {
             String::from("AAA = (BBB, BBB)"),
             String::from("BBB = (DDD, EEE)"),
         ];
-        let map = Map::new(lines);
+        let map = Map::new(lines).unwrap();
 
-        assert!(map.map.get("AAA").is_some());
-        assert!(map.map.get("BBB").is_some());
+        assert!(map.map.contains_key("AAA"));
+        assert!(map.map.contains_key("BBB"));
     }
 
     #[test]
@@ -59,12 +69,22 @@ mod test {
             String::from("BBB = (AAA, ZZZ)"),
             String::from("ZZZ = (ZZZ, ZZZ)"),
         ];
-        let map = Map::new(lines);
+        let map = Map::new(lines).unwrap();
+
+        assert_eq!(map.travel("AAA", &'L').unwrap(), "BBB");
+        assert_eq!(map.travel("BBB", &'L').unwrap(), "AAA");
+        assert_eq!(map.travel("AAA", &'R').unwrap(), "BBB");
+        assert_eq!(map.travel("BBB", &'R').unwrap(), "ZZZ");
+        assert_eq!(map.travel("ZZZ", &'L').unwrap(), "ZZZ");
+    }
+
+    #[test]
+    fn ch08_map_travel_unknown_location() {
+        let lines = vec![String::from("AAA = (BBB, BBB)")];
+        let map = Map::new(lines).unwrap();
 
-        assert_eq!(map.travel("AAA", &'L'), "BBB");
-        assert_eq!(map.travel("BBB", &'L'), "AAA");
-        assert_eq!(map.travel("AAA", &'R'), "BBB");
-        assert_eq!(map.travel("BBB", &'R'), "ZZZ");
-        assert_eq!(map.travel("ZZZ", &'L'), "ZZZ");
+        assert!(map.travel("AAA", &'L').is_ok());
+        assert!(map.travel("BBB", &'L').is_err());
+        assert!(map.travel("AAA", &'U').is_err());
     }
 }
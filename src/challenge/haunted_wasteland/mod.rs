@@ -1,72 +1,207 @@
-use crate::utils::to_lines_vec;
+use num::Integer;
 
-use self::map::Map;
+use crate::{answer::Answer, context::SolveContext, error::ChallengeError, utils::to_lines_vec};
 
-use super::Challenge;
-use std::fs::File;
+pub use self::{map::Map, node::Node};
+
+use super::{Challenge, Registration};
+use std::io::BufRead;
 
 mod map;
 mod node;
 
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+enum Solver {
+    #[default]
+    Lcm,
+    BruteForce,
+}
+
+pub const REGISTRATION: Registration = Registration {
+    year: 2023,
+    day: 8,
+    title: "Haunted Wasteland",
+    version: 1,
+    create: || Box::new(HauntedWasteland::default()),
+};
+
 #[derive(Default)]
 pub struct HauntedWasteland {
-    lines: Vec<String>,
+    directions: Vec<char>,
+    map: Map,
+    solver: Solver,
 }
 
 impl Challenge for HauntedWasteland {
-    fn load(&mut self, file: &File) {
-        self.lines = to_lines_vec(file);
+    fn load(&mut self, reader: &mut dyn BufRead) -> Result<(), ChallengeError> {
+        self.load_lines(to_lines_vec(reader)?)
     }
-    fn solve_part_one(&self) -> String {
-        let mut lines_iter = self.lines.iter();
-        let directions = lines_iter.next().expect("Could not parse directions.");
-        let map = Map::new(lines_iter.skip(1).map(|line| line.clone()).collect());
+    fn solve_part_one(&self, _context: &SolveContext) -> Result<Answer, ChallengeError> {
+        let steps = self
+            .steps_until("AAA", |location| location == "ZZZ")?
+            .ok_or_else(|| ChallengeError::solve("ZZZ cannot be reached from AAA."))?;
 
-        let mut path = directions.chars().cycle();
-        let mut steps = 1;
-        let mut location = "AAA";
-        loop {
-            location = map.travel(location, &path.next().expect("Path ended?"));
-            if location == "ZZZ" {
-                break;
+        Ok(Answer::from(steps))
+    }
+    fn solve_part_two(&self, context: &SolveContext) -> Result<Answer, ChallengeError> {
+        let steps = match self.solver {
+            Solver::Lcm => self.solve_part_two_lcm()?,
+            Solver::BruteForce => self.solve_part_two_brute_force(context)?,
+        };
+
+        Ok(Answer::from(steps))
+    }
+    fn solvers(&self) -> Vec<&'static str> {
+        vec!["lcm", "brute-force"]
+    }
+    fn use_solver(&mut self, name: &str) -> Result<(), ChallengeError> {
+        self.solver = match name {
+            "lcm" => Solver::Lcm,
+            "brute-force" => Solver::BruteForce,
+            _ => return Err(ChallengeError::unknown_solver(name, &self.solvers())),
+        };
+        Ok(())
+    }
+    fn queries(&self) -> Vec<&'static str> {
+        vec!["travel <node> <directions>"]
+    }
+    fn query(&self, command: &str, args: &[&str]) -> Result<String, ChallengeError> {
+        match (command, args) {
+            ("travel", [start, directions]) => {
+                let mut path = vec![start.to_string()];
+                let mut location = *start;
+                for direction in directions.chars() {
+                    location = self.map.travel(location, &direction)?;
+                    path.push(format!("-{}-> {}", direction, location));
+                }
+                Ok(path.join(" "))
             }
-            steps += 1;
+            ("travel", _) => Err(ChallengeError::solve("Usage: travel <node> <directions>")),
+            _ => Err(ChallengeError::unknown_command(command, &self.queries())),
+        }
+    }
+    fn parse_line(&self, line: &str) -> Option<Result<String, ChallengeError>> {
+        Some(Node::new(line).map(|node| format!("{:?}", node)))
+    }
+}
+
+impl HauntedWasteland {
+    fn load_lines(&mut self, lines: Vec<String>) -> Result<(), ChallengeError> {
+        let mut lines_iter = lines.into_iter();
+        let directions = lines_iter.next().ok_or_else(|| {
+            ChallengeError::parse("", 1, "Could not parse directions.").at_line(1)
+        })?;
+        if directions.is_empty() {
+            return Err(ChallengeError::parse("", 1, "Could not parse directions.").at_line(1));
+        }
+        if let Some((i, direction)) = directions
+            .char_indices()
+            .find(|(_, direction)| !matches!(direction, 'L' | 'R'))
+        {
+            return Err(ChallengeError::parse(
+                &direction.to_string(),
+                i + 1,
+                "Directions must be L or R.",
+            )
+            .at_line(1));
         }
 
-        format!("{}", steps)
+        self.directions = directions.chars().collect();
+        self.map = Map::new(lines_iter.skip(1).collect()).map_err(|e| e.offset_lines(2))?;
+        Ok(())
     }
-    fn solve_part_two(&self) -> String {
-        let mut lines_iter = self.lines.iter();
-        let directions = lines_iter.next().expect("Could not parse directions.");
-        let map = Map::new(lines_iter.skip(1).map(|line| line.clone()).collect());
 
-        let mut path = directions.chars().cycle();
-        let mut locations: Vec<&str> = map
+    // Without any ghosts, both solvers would report 1 instead of failing.
+    fn starting_locations(&self) -> Result<Vec<&str>, ChallengeError> {
+        let locations: Vec<&str> = self
+            .map
             .iter()
             .filter(|(_, v)| v.matches("__A"))
             .map(|(_, v)| v.address.as_str())
             .collect();
+        if locations.is_empty() {
+            return Err(ChallengeError::solve("No starting locations ending in A."));
+        }
+
+        Ok(locations)
+    }
+
+    // None when no end can be reached, so callers can say which end.
+    fn steps_until(
+        &self,
+        start: &str,
+        is_end: impl Fn(&str) -> bool,
+    ) -> Result<Option<u64>, ChallengeError> {
+        let mut path = self.directions.iter().cycle();
+        let max_steps = (self.directions.len() * self.map.len()) as u64;
+        let mut steps = 1;
+        let mut location = start;
+        loop {
+            location = self.map.travel(location, self.next_direction(&mut path)?)?;
+            if is_end(location) {
+                return Ok(Some(steps));
+            }
+            if steps > max_steps {
+                return Ok(None);
+            }
+            steps += 1;
+        }
+    }
+
+    // Each ghost reaches its __Z node on a fixed cycle, so they all line up
+    // on the least common multiple of their individual path lengths.
+    fn solve_part_two_lcm(&self) -> Result<u64, ChallengeError> {
+        self.starting_locations()?
+            .into_iter()
+            .map(|start| {
+                self.steps_until(start, |location| location.ends_with('Z'))?
+                    .ok_or_else(|| {
+                        ChallengeError::solve(&format!(
+                            "No end location can be reached from {}.",
+                            start
+                        ))
+                    })
+            })
+            .try_fold(1u64, |acc, steps| Ok(acc.lcm(&steps?)))
+    }
+
+    // Ghosts that never line up would loop forever, so this checks the
+    // context's time budget as it goes.
+    fn solve_part_two_brute_force(&self, context: &SolveContext) -> Result<u64, ChallengeError> {
+        let mut path = self.directions.iter().cycle();
+        let mut locations = self.starting_locations()?;
         let mut steps = 1;
 
         loop {
             if steps % 1000000 == 0 {
-                println!("Travelled {} steps.", steps);
+                context
+                    .trace()
+                    .progress(format_args!("Travelled {} steps.", steps));
             }
-            let next_path = path.next().expect("Path ended?");
+            if steps % 65536 == 0 {
+                context.tick(steps)?;
+            }
+            let next_path = self.next_direction(&mut path)?;
             locations = locations
                 .into_iter()
-                .map(|location| map.travel(location, &next_path))
-                .collect();
+                .map(|location| self.map.travel(location, next_path))
+                .collect::<Result<Vec<&str>, ChallengeError>>()?;
             if locations
                 .iter()
                 .all(|location| location.chars().nth(2) == Some('Z'))
             {
-                break;
+                return Ok(steps);
             }
             steps += 1;
         }
+    }
 
-        format!("{}", steps)
+    fn next_direction<'a>(
+        &self,
+        path: &mut impl Iterator<Item = &'a char>,
+    ) -> Result<&'a char, ChallengeError> {
+        path.next()
+            .ok_or_else(|| ChallengeError::solve("Path ended?"))
     }
 }
 
@@ -74,6 +209,20 @@ impl Challenge for HauntedWasteland {
 mod test {
     use super::*;
 
+    #[test]
+    fn ch08_haunted_wasteland_travel_query() {
+        let mut haunted_wasteland = HauntedWasteland::default();
+        haunted_wasteland
+            .load_str("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)")
+            .unwrap();
+
+        assert_eq!(
+            haunted_wasteland.query("travel", &["AAA", "LR"]).unwrap(),
+            "AAA -L-> BBB -R-> ZZZ"
+        );
+        assert!(haunted_wasteland.query("travel", &["QQQ", "L"]).is_err());
+    }
+
     fn get_lines() -> Vec<String> {
         vec![
             String::from("RL"),
@@ -90,30 +239,139 @@ mod test {
 
     #[test]
     fn ch08_haunted_wasteland_part_one() {
-        let lines = get_lines();
         let mut haunted_wasteland = HauntedWasteland::default();
-        haunted_wasteland.lines = lines;
+        haunted_wasteland.load_str(&get_lines().join("\n")).unwrap();
 
-        assert_eq!(haunted_wasteland.solve_part_one(), "2");
+        assert_eq!(haunted_wasteland.solve_part_one(&SolveContext::default()).unwrap(), "2");
     }
 
     #[test]
     fn ch08_haunted_wasteland_part_two() {
-        let lines = vec![
-            String::from("LR"),
-            String::from(""),
-            String::from("11A = (11B, XXX)"),
-            String::from("11B = (XXX, 11Z)"),
-            String::from("11Z = (11B, XXX)"),
-            String::from("22A = (22B, XXX)"),
-            String::from("22B = (22C, 22C)"),
-            String::from("22C = (22Z, 22Z)"),
-            String::from("22Z = (22B, 22B)"),
-            String::from("XXX = (XXX, XXX)"),
+        let lines = [
+            "LR",
+            "",
+            "11A = (11B, XXX)",
+            "11B = (XXX, 11Z)",
+            "11Z = (11B, XXX)",
+            "22A = (22B, XXX)",
+            "22B = (22C, 22C)",
+            "22C = (22Z, 22Z)",
+            "22Z = (22B, 22B)",
+            "XXX = (XXX, XXX)",
         ];
         let mut haunted_wasteland = HauntedWasteland::default();
-        haunted_wasteland.lines = lines;
+        haunted_wasteland.load_str(&lines.join("\n")).unwrap();
+
+        assert_eq!(haunted_wasteland.solve_part_two(&SolveContext::default()).unwrap(), "6");
+
+        haunted_wasteland.use_solver("brute-force").unwrap();
+        assert_eq!(haunted_wasteland.solve_part_two(&SolveContext::default()).unwrap(), "6");
+    }
+
+    #[test]
+    fn ch08_haunted_wasteland_unknown_solver() {
+        let mut haunted_wasteland = HauntedWasteland::default();
+        let error = haunted_wasteland.use_solver("fast").unwrap_err();
+
+        assert_eq!(
+            error,
+            ChallengeError::solve("Unknown solver 'fast'. Available solvers: lcm, brute-force")
+        );
+        assert_eq!(haunted_wasteland.solver, Solver::Lcm);
+    }
+
+    #[test]
+    fn ch08_haunted_wasteland_load_bad_direction() {
+        let mut lines = get_lines();
+        lines[0] = String::from("RLX");
+
+        let mut haunted_wasteland = HauntedWasteland::default();
+        let error = haunted_wasteland.load_str(&lines.join("\n")).unwrap_err();
+        assert_eq!(
+            error,
+            ChallengeError::parse("X", 3, "Directions must be L or R.").at_line(1)
+        );
+    }
+
+    #[test]
+    fn ch08_haunted_wasteland_load_reports_node_line() {
+        let mut lines = get_lines();
+        lines[4] = String::from("CCC (ZZZ, GGG)");
+
+        let mut haunted_wasteland = HauntedWasteland::default();
+        let error = haunted_wasteland.load_str(&lines.join("\n")).unwrap_err();
+        assert_eq!(
+            error,
+            ChallengeError::parse("CCC (ZZZ, GGG)", 1, "Expected '<node> = (<left>, <right>)'")
+                .at_line(5)
+        );
+    }
+
+    #[test]
+    fn ch08_haunted_wasteland_brute_force_times_out() {
+        let lines = [
+            "L",
+            "",
+            "11A = (11Z, 11Z)",
+            "11Z = (11A, 11A)",
+            "22A = (22B, 22B)",
+            "22B = (22A, 22A)",
+        ];
+        let mut haunted_wasteland = HauntedWasteland::default();
+        haunted_wasteland.load_str(&lines.join("\n")).unwrap();
+        haunted_wasteland.use_solver("brute-force").unwrap();
+
+        let context = SolveContext::with_timeout(std::time::Duration::ZERO);
+        let error = haunted_wasteland.solve_part_two(&context).unwrap_err();
+        assert_eq!(
+            error,
+            ChallengeError::TimedOut {
+                day: None,
+                timeout: std::time::Duration::ZERO,
+                progress: Some(65536),
+            }
+        );
+    }
+
+    #[test]
+    fn ch08_haunted_wasteland_part_two_without_starts() {
+        let lines = ["L", "", "11B = (11Z, 11Z)", "11Z = (11B, 11B)"];
+        let mut haunted_wasteland = HauntedWasteland::default();
+        haunted_wasteland.load_str(&lines.join("\n")).unwrap();
+        let expected = Err(ChallengeError::solve("No starting locations ending in A."));
+
+        assert_eq!(
+            haunted_wasteland.solve_part_two(&SolveContext::default()),
+            expected
+        );
+        haunted_wasteland.use_solver("brute-force").unwrap();
+        assert_eq!(
+            haunted_wasteland.solve_part_two(&SolveContext::default()),
+            expected
+        );
+    }
+
+    #[test]
+    fn ch08_haunted_wasteland_part_one_unreachable() {
+        let lines = ["L", "", "AAA = (AAA, ZZZ)", "ZZZ = (ZZZ, ZZZ)"];
+        let mut haunted_wasteland = HauntedWasteland::default();
+        haunted_wasteland.load_str(&lines.join("\n")).unwrap();
+
+        assert_eq!(
+            haunted_wasteland.solve_part_one(&SolveContext::default()),
+            Err(ChallengeError::solve("ZZZ cannot be reached from AAA."))
+        );
+    }
+
+    #[test]
+    fn ch08_haunted_wasteland_part_one_keeps_travel_errors() {
+        let lines = ["L", "", "AAA = (QQQ, ZZZ)", "ZZZ = (ZZZ, ZZZ)"];
+        let mut haunted_wasteland = HauntedWasteland::default();
+        haunted_wasteland.load_str(&lines.join("\n")).unwrap();
 
-        assert_eq!(haunted_wasteland.solve_part_two(), "6");
+        assert_eq!(
+            haunted_wasteland.solve_part_one(&SolveContext::default()),
+            Err(ChallengeError::solve("Got lost at unknown location: QQQ"))
+        );
     }
 }
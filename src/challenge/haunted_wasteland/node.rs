@@ -1,3 +1,5 @@
+use crate::error::ChallengeError;
+
 #[derive(Debug, PartialEq)]
 pub struct Node {
     pub address: String,
@@ -6,24 +8,35 @@ pub struct Node {
 }
 
 impl Node {
-    pub fn new(line: String) -> Self {
+    pub fn new(line: &str) -> Result<Self, ChallengeError> {
         // AAA = (BBB, BBB)
-        let address = line[..3].to_string();
-        let left_address = line[7..10].to_string();
-        let right_address = line[12..15].to_string();
+        let (address, targets) = line.split_once(" = ").ok_or_else(|| {
+            ChallengeError::parse(line, 1, "Expected '<node> = (<left>, <right>)'")
+        })?;
+        let (left_address, right_address) = targets
+            .strip_prefix('(')
+            .and_then(|targets| targets.strip_suffix(')'))
+            .and_then(|targets| targets.split_once(", "))
+            .ok_or_else(|| {
+                ChallengeError::parse(
+                    targets,
+                    address.len() + " = ".len() + 1,
+                    "Expected '(<left>, <right>)'",
+                )
+            })?;
 
-        Node {
-            address,
-            left_address,
-            right_address,
-        }
+        Ok(Node {
+            address: address.to_string(),
+            left_address: left_address.to_string(),
+            right_address: right_address.to_string(),
+        })
     }
 
-    pub fn get_next(&self, dir: &char) -> &String {
+    pub fn get_next(&self, dir: &char) -> Option<&String> {
         match dir {
-            'L' => &self.left_address,
-            'R' => &self.right_address,
-            _ => panic!("Invalid character in directions."),
+            'L' => Some(&self.left_address),
+            'R' => Some(&self.right_address),
+            _ => None,
         }
     }
 
@@ -41,7 +54,7 @@ mod test {
 
     #[test]
     fn ch08_node_new() {
-        let node = Node::new(String::from("AAA = (BBB, CCC)"));
+        let node = Node::new("AAA = (BBB, CCC)").unwrap();
         let expected = Node {
             address: String::from("AAA"),
             left_address: String::from("BBB"),
@@ -52,14 +65,15 @@ mod test {
 
     #[test]
     fn ch08_node_get_next() {
-        let node = Node::new(String::from("AAA = (BBB, CCC)"));
-        assert_eq!(node.get_next(&'L'), "BBB");
-        assert_eq!(node.get_next(&'R'), "CCC");
+        let node = Node::new("AAA = (BBB, CCC)").unwrap();
+        assert_eq!(node.get_next(&'L').unwrap(), "BBB");
+        assert_eq!(node.get_next(&'R').unwrap(), "CCC");
+        assert_eq!(node.get_next(&'U'), None);
     }
 
     #[test]
     fn ch08_map_matches() {
-        let node = Node::new(String::from("ABC = (BBB, BBB)"));
+        let node = Node::new("ABC = (BBB, BBB)").unwrap();
 
         assert!(node.matches("ABC"));
         assert!(node.matches("A_C"));
@@ -70,4 +84,13 @@ mod test {
         assert!(!node.matches("__Z"));
         assert!(!node.matches("_BF"));
     }
+
+    #[test]
+    fn ch08_node_new_malformed() {
+        let error = Node::new("AAA = BBB, CCC").unwrap_err();
+        assert_eq!(
+            error,
+            ChallengeError::parse("BBB, CCC", 7, "Expected '(<left>, <right>)'")
+        );
+    }
 }
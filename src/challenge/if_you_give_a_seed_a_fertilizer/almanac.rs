@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use super::{resource::Resource, resource_map::ResourceMap};
+use crate::error::ChallengeError;
 
 #[derive(Default)]
 pub struct Almanac {
@@ -8,26 +9,47 @@ pub struct Almanac {
 }
 
 impl Almanac {
-    pub fn new(lines: Vec<String>) -> Self {
+    pub fn new(lines: Vec<String>) -> Result<Self, ChallengeError> {
         let mut maps = HashMap::new();
 
-        lines.split(|el| el.is_empty()).for_each(|group| {
-            let resource_map = ResourceMap::new(group.into());
-            maps.insert(resource_map.get_from(), resource_map);
-        });
+        let mut line_offset = 0;
+        for group in lines.split(|el| el.is_empty()) {
+            if !group.is_empty() {
+                let resource_map =
+                    ResourceMap::new(group.into()).map_err(|e| e.offset_lines(line_offset))?;
+                maps.insert(resource_map.get_from(), resource_map);
+            }
+            line_offset += group.len() + 1;
+        }
 
-        Almanac { maps }
+        Ok(Almanac { maps })
     }
 
-    pub fn condense(&self) -> ResourceMap {
+    pub fn condense(&self) -> Result<ResourceMap, ChallengeError> {
         let mut maps = self.maps.clone();
-        let mut current = maps.remove(&Resource::Seed).expect("No seed in map.");
+        let mut current = maps
+            .remove(&Resource::Seed)
+            .ok_or_else(|| ChallengeError::solve("No seed map in almanac."))?;
         while let Some(mut next) = maps.remove(&current.get_to()) {
             next.merge_maps(&current);
             current = next;
         }
 
-        current
+        Ok(current)
+    }
+
+    // Every resource the value passes through, starting with `from` itself.
+    pub fn map_path(&self, value: num::BigInt, from: Resource) -> Vec<(Resource, num::BigInt)> {
+        let mut path = vec![(from, value)];
+        while let Some(resource_map) = path
+            .last()
+            .and_then(|(resource, _)| self.maps.get(resource))
+        {
+            let (value, resource) = resource_map.map_to(path[path.len() - 1].1.clone());
+            path.push((resource, value));
+        }
+
+        path
     }
 
     pub fn map_through(&self, seed: num::BigInt, from: Resource) -> (num::BigInt, Resource) {
@@ -57,7 +79,7 @@ mod test {
             String::from("37 52 2"),
         ];
 
-        Almanac::new(lines)
+        Almanac::new(lines).unwrap()
     }
 
     #[test]
@@ -65,8 +87,8 @@ mod test {
         let almanac = create_test_case();
 
         assert_eq!(almanac.maps.len(), 2);
-        assert!(almanac.maps.get(&Resource::Seed).is_some());
-        assert!(almanac.maps.get(&Resource::Soil).is_some());
+        assert!(almanac.maps.contains_key(&Resource::Seed));
+        assert!(almanac.maps.contains_key(&Resource::Soil));
     }
 
     #[test]
@@ -77,4 +99,36 @@ mod test {
         assert_eq!(value, num::BigInt::from(81));
         assert_eq!(resource, Resource::Fertilizer);
     }
+
+    #[test]
+    fn ch05_almanac_map_path() {
+        let almanac = create_test_case();
+
+        let path = almanac.map_path(num::BigInt::from(79), Resource::Seed);
+        assert_eq!(
+            path,
+            vec![
+                (Resource::Seed, num::BigInt::from(79)),
+                (Resource::Soil, num::BigInt::from(81)),
+                (Resource::Fertilizer, num::BigInt::from(81)),
+            ]
+        );
+    }
+
+    #[test]
+    fn ch05_almanac_new_reports_line() {
+        let lines = vec![
+            String::from("seed-to-soil map:"),
+            String::from("50 98 2"),
+            String::from(""),
+            String::from("soil-to-fertilizer map:"),
+            String::from("0 15 x"),
+        ];
+
+        let error = Almanac::new(lines).err().unwrap();
+        assert_eq!(
+            error,
+            ChallengeError::parse("x", 6, "Could not parse range value").at_line(5)
+        );
+    }
 }
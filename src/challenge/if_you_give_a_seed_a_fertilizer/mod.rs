@@ -1,14 +1,26 @@
-use crate::utils::to_lines_vec;
+use crate::{answer::Answer, context::SolveContext, error::ChallengeError, utils::{split_offsets, to_lines_vec}};
 
-use self::{almanac::Almanac, resource::Resource};
+pub use self::{
+    almanac::Almanac,
+    resource::Resource,
+    resource_map::{MapRange, ResourceMap},
+};
 
-use super::Challenge;
-use std::fs::File;
+use super::{Challenge, Registration};
+use std::io::BufRead;
 
 mod almanac;
 mod resource;
 mod resource_map;
 
+pub const REGISTRATION: Registration = Registration {
+    year: 2023,
+    day: 5,
+    title: "If You Give A Seed A Fertilizer",
+    version: 1,
+    create: || Box::new(IfYouGiveASeedAFertilizer::default()),
+};
+
 #[derive(Default)]
 pub struct IfYouGiveASeedAFertilizer {
     seeds: Vec<num::BigInt>,
@@ -16,47 +28,98 @@ pub struct IfYouGiveASeedAFertilizer {
 }
 
 impl Challenge for IfYouGiveASeedAFertilizer {
-    fn load(&mut self, file: &File) {
-        let lines = to_lines_vec(file);
-        self.load_lines(lines);
+    fn load(&mut self, reader: &mut dyn BufRead) -> Result<(), ChallengeError> {
+        let lines = to_lines_vec(reader)?;
+        self.load_lines(lines)
     }
-    fn solve_part_one(&self) -> String {
-        let min_location = self
-            .seeds
-            .iter()
-            .map(|seed| {
-                let (value, resource) = self.almanac.map_through(seed.clone(), Resource::Seed);
-                if resource != Resource::Location {
-                    panic!("Could not map seed to location.");
-                }
-                value
-            })
+    fn solve_part_one(&self, _context: &SolveContext) -> Result<Answer, ChallengeError> {
+        let mut locations = Vec::new();
+        for seed in &self.seeds {
+            let (value, resource) = self.almanac.map_through(seed.clone(), Resource::Seed);
+            if resource != Resource::Location {
+                return Err(ChallengeError::solve("Could not map seed to location."));
+            }
+            locations.push(value);
+        }
+
+        let min_location = locations
+            .into_iter()
             .min()
-            .expect("No elements in location calculation.");
+            .ok_or_else(|| ChallengeError::solve("No elements in location calculation."))?;
 
-        format!("{}", min_location)
+        Ok(Answer::from(min_location))
     }
-    fn solve_part_two(&self) -> String {
-        let resource_map = self.almanac.condense();
+    fn solve_part_two(&self, _context: &SolveContext) -> Result<Answer, ChallengeError> {
+        if self.seeds.len() % 2 != 0 {
+            return Err(ChallengeError::solve(
+                "Seed ranges must come in start and length pairs.",
+            ));
+        }
+
+        let resource_map = self.almanac.condense()?;
         let min_location = self
             .seeds
             .chunks(2)
-            .map(|range| resource_map.lowest_overlap(&range[0], &range[1]))
+            .map(|range| {
+                resource_map.lowest_overlap(&range[0], &range[1]).ok_or_else(|| {
+                    ChallengeError::solve(&format!(
+                        "Seed range starting at {} must have a positive length.",
+                        range[0]
+                    ))
+                })
+            })
+            .collect::<Result<Vec<num::BigInt>, ChallengeError>>()?
+            .into_iter()
             .min()
-            .expect("Could not map through seed value.");
+            .ok_or_else(|| ChallengeError::solve("Could not map through seed value."))?;
 
-        format!("{}", min_location)
+        Ok(Answer::from(min_location))
+    }
+    fn queries(&self) -> Vec<&'static str> {
+        vec!["map <resource> <value>"]
+    }
+    fn query(&self, command: &str, args: &[&str]) -> Result<String, ChallengeError> {
+        match (command, args) {
+            ("map", [resource, value]) => self.query_map(resource, value),
+            ("map", _) => Err(ChallengeError::solve("Usage: map <resource> <value>")),
+            _ => Err(ChallengeError::unknown_command(command, &self.queries())),
+        }
     }
 }
 
 impl IfYouGiveASeedAFertilizer {
-    fn load_lines(&mut self, lines: Vec<String>) {
-        self.seeds = lines[0]
-            .split(' ')
-            .skip(1)
-            .map(|num_str| num_str.parse().expect("Could not parse seed into number."))
+    fn query_map(&self, resource: &str, value: &str) -> Result<String, ChallengeError> {
+        let resource = Resource::from(resource)?;
+        let value = value
+            .parse::<num::BigInt>()
+            .map_err(|_| ChallengeError::parse(value, 1, "Could not parse value"))?;
+
+        let path: Vec<String> = self
+            .almanac
+            .map_path(value, resource)
+            .iter()
+            .map(|(resource, value)| format!("{} {}", resource, value))
             .collect();
-        self.almanac = Almanac::new(lines.into_iter().skip(2).collect());
+        Ok(path.join(" -> "))
+    }
+
+    fn load_lines(&mut self, lines: Vec<String>) -> Result<(), ChallengeError> {
+        let seeds_line = lines
+            .first()
+            .ok_or_else(|| ChallengeError::parse("", 1, "Expected a seeds line").at_line(1))?;
+        self.seeds = split_offsets(seeds_line, ' ')
+            .skip(1)
+            .filter(|(_, num_str)| !num_str.is_empty())
+            .map(|(offset, num_str)| {
+                num_str.parse().map_err(|_| {
+                    ChallengeError::parse(num_str, offset + 1, "Could not parse seed into number.")
+                        .at_line(1)
+                })
+            })
+            .collect::<Result<Vec<num::BigInt>, ChallengeError>>()?;
+        self.almanac =
+            Almanac::new(lines.into_iter().skip(2).collect()).map_err(|e| e.offset_lines(2))?;
+        Ok(())
     }
 }
 #[cfg(test)]
@@ -64,7 +127,7 @@ mod test {
     use super::*;
 
     fn get_test_input() -> IfYouGiveASeedAFertilizer {
-        let lines = vec![
+        let lines = [
             "seeds: 79 14 55 13",
             "",
             "seed-to-soil map:",
@@ -98,25 +161,62 @@ mod test {
             "humidity-to-location map:",
             "60 56 37",
             "56 93 4",
-        ]
-        .iter()
-        .map(|line| line.to_string())
-        .collect();
+        ];
 
         let mut seed_fertilizer = IfYouGiveASeedAFertilizer::default();
-        seed_fertilizer.load_lines(lines);
+        seed_fertilizer.load_str(&lines.join("\n")).unwrap();
         seed_fertilizer
     }
 
     #[test]
     fn ch05_seed_fertilizer_part_one() {
         let seed_fertilizer = get_test_input();
-        assert_eq!(seed_fertilizer.solve_part_one(), "35");
+        assert_eq!(seed_fertilizer.solve_part_one(&SolveContext::default()).unwrap(), "35");
     }
 
     #[test]
     fn cho5_seed_fertilizer_part_two() {
         let seed_fertilizer = get_test_input();
-        assert_eq!(seed_fertilizer.solve_part_two(), "46");
+        assert_eq!(seed_fertilizer.solve_part_two(&SolveContext::default()).unwrap(), "46");
+    }
+
+    #[test]
+    fn ch05_seed_fertilizer_load_reports_line() {
+        let lines = [
+            "seeds: 79 14",
+            "",
+            "seed-to-soil map:",
+            "50 98 2",
+            "",
+            "soil-to-fertilizer map:",
+            "0 15 37",
+            "37 52",
+        ];
+
+        let mut seed_fertilizer = IfYouGiveASeedAFertilizer::default();
+        let error = seed_fertilizer.load_str(&lines.join("\n")).unwrap_err();
+        assert_eq!(
+            error,
+            ChallengeError::parse("37 52", 1, "Expected three range values").at_line(8)
+        );
+    }
+
+    #[test]
+    fn ch05_seed_fertilizer_part_two_odd_seeds() {
+        let mut seed_fertilizer = get_test_input();
+        seed_fertilizer.seeds.pop();
+        assert!(seed_fertilizer.solve_part_two(&SolveContext::default()).is_err());
+    }
+
+    #[test]
+    fn ch05_seed_fertilizer_part_two_empty_range() {
+        let mut seed_fertilizer = get_test_input();
+        seed_fertilizer.seeds = vec![num::BigInt::from(79), num::BigInt::from(0)];
+        assert_eq!(
+            seed_fertilizer.solve_part_two(&SolveContext::default()),
+            Err(ChallengeError::solve(
+                "Seed range starting at 79 must have a positive length."
+            ))
+        );
     }
 }
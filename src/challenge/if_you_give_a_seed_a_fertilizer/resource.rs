@@ -1,5 +1,7 @@
 use std::fmt::Display;
 
+use crate::error::ChallengeError;
+
 #[derive(Eq, PartialEq, Hash, Debug, Clone, Copy)]
 pub enum Resource {
     Seed,
@@ -32,17 +34,21 @@ impl Display for Resource {
 }
 
 impl Resource {
-    pub fn from(resource_str: &str) -> Self {
+    pub fn from(resource_str: &str) -> Result<Self, ChallengeError> {
         match resource_str {
-            "seed" => Resource::Seed,
-            "soil" => Resource::Soil,
-            "fertilizer" => Resource::Fertilizer,
-            "water" => Resource::Water,
-            "light" => Resource::Light,
-            "temperature" => Resource::Temperature,
-            "humidity" => Resource::Humidity,
-            "location" => Resource::Location,
-            _ => panic!("Attempted to parse an invalid resource: {}", resource_str),
+            "seed" => Ok(Resource::Seed),
+            "soil" => Ok(Resource::Soil),
+            "fertilizer" => Ok(Resource::Fertilizer),
+            "water" => Ok(Resource::Water),
+            "light" => Ok(Resource::Light),
+            "temperature" => Ok(Resource::Temperature),
+            "humidity" => Ok(Resource::Humidity),
+            "location" => Ok(Resource::Location),
+            _ => Err(ChallengeError::parse(
+                resource_str,
+                1,
+                "Attempted to parse an invalid resource",
+            )),
         }
     }
 }
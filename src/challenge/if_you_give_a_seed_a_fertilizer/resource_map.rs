@@ -1,4 +1,5 @@
 use super::resource::Resource;
+use crate::{error::ChallengeError, utils::split_offsets};
 
 #[derive(Debug, Clone)]
 pub struct ResourceMap {
@@ -8,108 +9,114 @@ pub struct ResourceMap {
 }
 
 impl ResourceMap {
-    pub fn new(lines: Vec<String>) -> Self {
-        let mut line_iter = lines.iter();
-
-        let mut first_line_iter = line_iter
-            .next()
-            .expect("No lines to extract.")
-            .split(' ')
-            .next()
-            .expect("Error extracting map type.")
-            .split("-to-");
-        let from_str = first_line_iter
-            .next()
-            .expect("Could not extract 'from' value from string.");
-        let to_str = first_line_iter
-            .next()
-            .expect("Could not extract 'to' from string.");
-
-        let ranges = line_iter.map(|line| MapRange::from(line)).collect();
-
-        ResourceMap {
-            from: Resource::from(from_str),
-            to: Resource::from(to_str),
-            ranges,
-        }
+    pub fn new(lines: Vec<String>) -> Result<Self, ChallengeError> {
+        let header = lines
+            .first()
+            .ok_or_else(|| ChallengeError::parse("", 1, "No lines to extract"))?;
+        let map_type = header.split(' ').next().unwrap_or(header);
+        let (from_str, to_str) = map_type.split_once("-to-").ok_or_else(|| {
+            ChallengeError::parse(map_type, 1, "Expected a '<from>-to-<to> map:' header")
+                .at_line(1)
+        })?;
+        let from = Resource::from(from_str).map_err(|e| e.at_line(1))?;
+        let to = Resource::from(to_str)
+            .map_err(|e| e.offset_columns(from_str.len() + "-to-".len()).at_line(1))?;
+
+        let ranges = lines
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, line)| MapRange::from(line).map_err(|e| e.at_line(i + 1)))
+            .collect::<Result<Vec<MapRange>, ChallengeError>>()?;
+
+        Ok(ResourceMap { from, to, ranges })
     }
 
     pub fn map_to(&self, from: num::BigInt) -> (num::BigInt, Resource) {
         let mut to = from.clone();
         for range in &self.ranges {
-            if range.contains(&from) {
-                to = range.map_to(&from);
+            if let Some(value) = range.map_to(&from) {
+                to = value;
             }
         }
 
-        (to, self.to.clone())
+        (to, self.to)
     }
 
     pub fn get_from(&self) -> Resource {
-        self.from.clone()
+        self.from
     }
 
     pub fn get_to(&self) -> Resource {
-        self.to.clone()
-    }
-
-    pub fn get_ranges(&self) -> &Vec<MapRange> {
-        &self.ranges
+        self.to
     }
 
     pub fn merge_maps(&mut self, previous: &ResourceMap) {
-        self.from = previous.get_from();
         self.normalize(previous);
-        self.ranges.iter_mut().for_each(|range| {
-            for prev_range in previous.get_ranges() {
-                if prev_range.contains_to(&range.from_start) {
-                    range.add(&prev_range.get_diff());
-                }
-            }
-        })
+        self.from = previous.get_from();
     }
 
     fn normalize(&mut self, previous: &ResourceMap) {
         let mut split_values: Vec<num::BigInt> = previous
             .ranges
             .iter()
-            .flat_map(|range| [range.from_start.clone(), range.from_end()].into_iter())
+            .flat_map(|range| [range.from_start.clone(), range.get_from_end()].into_iter())
+            .chain(self.ranges.iter().flat_map(|range| {
+                [range.from_start.clone(), range.get_from_end()]
+                    .into_iter()
+                    .flat_map(|value| previous.map_from(&value))
+            }))
             .collect();
         split_values.sort();
-        let new_ranges = self
-            .ranges
-            .iter()
-            .flat_map(|range| {
-                let mut parts = Vec::new();
-                let mut right = range.clone();
-                let mut i = 0;
-                while i < split_values.len() {
-                    if let Some((l, r)) = right.split_on(&split_values[i]) {
-                        right = r;
-                        parts.push(l)
-                    }
-
-                    i += 1;
+        split_values.dedup();
+
+        let new_ranges = split_values
+            .windows(2)
+            .map(|bounds| {
+                let (through, _) = previous.map_to(bounds[0].clone());
+                let (to_start, _) = self.map_to(through);
+                MapRange {
+                    from_start: bounds[0].clone(),
+                    to_start,
+                    length: &bounds[1] - &bounds[0],
                 }
-                parts.push(right);
-                parts.into_iter()
             })
             .collect();
 
         self.ranges = new_ranges;
     }
 
-    pub fn lowest_overlap(&self, start: &num::BigInt, length: &num::BigInt) -> num::BigInt {
+    fn map_from(&self, to: &num::BigInt) -> Vec<num::BigInt> {
+        let mut from: Vec<num::BigInt> = self
+            .ranges
+            .iter()
+            .filter(|range| range.contains_to(to))
+            .map(|range| &range.from_start + (to - &range.to_start))
+            .collect();
+        if !self.ranges.iter().any(|range| range.contains(to)) {
+            from.push(to.clone());
+        }
+
+        from
+    }
+
+    // None when the seed range is empty.
+    pub fn lowest_overlap(&self, start: &num::BigInt, length: &num::BigInt) -> Option<num::BigInt> {
+        if length <= &num::BigInt::from(0) {
+            return None;
+        }
+
         let map_range = MapRange {
             from_start: start.clone(),
             to_start: start.clone(),
             length: length.clone(),
         };
-        let split_values: Vec<num::BigInt> = self
+        let mut split_values: Vec<num::BigInt> = self
             .ranges
             .iter()
-            .flat_map(|range| [range.from_start.clone(), range.from_end()].into_iter())
+            .flat_map(|range| [range.from_start.clone(), range.get_from_end()].into_iter())
             .collect();
+        split_values.sort();
         [map_range]
             .iter()
             .flat_map(|range| {
@@ -127,10 +134,9 @@ impl ResourceMap {
                 parts.push(right);
                 parts.into_iter()
             })
-            .filter(|range| &range.from_start >= start && range.from_end() <= start + length)
+            .filter(|range| &range.from_start >= start && range.get_from_end() <= start + length)
             .map(|range| self.map_to(range.from_start).0)
             .min()
-            .expect("No values to calculate minimum in map range.")
     }
 }
 
@@ -142,26 +148,28 @@ pub struct MapRange {
 }
 
 impl MapRange {
-    pub fn from(line: &str) -> Self {
-        let mut values = line.split(' ');
-
-        MapRange {
-            to_start: values
-                .next()
-                .expect("Invalid MapRange string.")
-                .parse()
-                .expect("Could not parse range value into number."),
-            from_start: values
-                .next()
-                .expect("Invalid MapRange string.")
-                .parse()
-                .expect("Could not parse range value into number."),
-            length: values
-                .next()
-                .expect("Invalid MapRange string.")
-                .parse()
-                .expect("Could not parse range value into number."),
-        }
+    pub fn from(line: &str) -> Result<Self, ChallengeError> {
+        let mut values = split_offsets(line, ' ')
+            .filter(|(_, v)| !v.is_empty())
+            .map(|(offset, v)| {
+                v.parse::<num::BigInt>()
+                    .map_err(|_| ChallengeError::parse(v, offset + 1, "Could not parse range value"))
+            });
+        let mut next_value = || {
+            values.next().unwrap_or_else(|| {
+                Err(ChallengeError::parse(
+                    line,
+                    1,
+                    "Expected three range values",
+                ))
+            })
+        };
+
+        Ok(MapRange {
+            to_start: next_value()?,
+            from_start: next_value()?,
+            length: next_value()?,
+        })
     }
 
     pub fn contains(&self, value: &num::BigInt) -> bool {
@@ -174,16 +182,10 @@ impl MapRange {
         range.contains(&value)
     }
 
-    pub fn get_diff(&self) -> num::BigInt {
-        &self.to_start - &self.from_start
-    }
-
-    pub fn map_to(&self, from: &num::BigInt) -> num::BigInt {
-        if !self.contains(from) {
-            panic!("Attempted to map a value that is not in the range.");
-        }
-        let from_diff = from - &self.from_start;
-        &self.to_start + from_diff
+    // None when `from` is outside the range.
+    pub fn map_to(&self, from: &num::BigInt) -> Option<num::BigInt> {
+        self.contains(from)
+            .then(|| &self.to_start + (from - &self.from_start))
     }
 
     pub fn can_split(&self, value: &num::BigInt) -> bool {
@@ -211,14 +213,9 @@ impl MapRange {
         Some((left, right))
     }
 
-    pub fn from_end(&self) -> num::BigInt {
+    pub fn get_from_end(&self) -> num::BigInt {
         &self.from_start + &self.length
     }
-
-    pub fn add(&mut self, value: &num::BigInt) {
-        let start = &self.from_start + value;
-        self.from_start = start.clone();
-    }
 }
 
 #[cfg(test)]
@@ -228,7 +225,7 @@ mod test {
     #[test]
     fn ch05_map_range_from() {
         let line = "45 77 23";
-        let map_range = MapRange::from(line);
+        let map_range = MapRange::from(line).unwrap();
 
         assert_eq!(map_range.from_start, num::BigInt::from(77));
         assert_eq!(map_range.to_start, num::BigInt::from(45));
@@ -238,7 +235,7 @@ mod test {
     #[test]
     fn ch05_map_range_split_on() {
         let line = "45 77 23";
-        let map_range = MapRange::from(line);
+        let map_range = MapRange::from(line).unwrap();
         let (left, right) = map_range.split_on(&num::BigInt::from(80)).unwrap();
 
         assert_eq!(left.from_start, num::BigInt::from(77));
@@ -252,7 +249,7 @@ mod test {
     #[test]
     fn ch05_map_range_contains() {
         let line = "45 77 23";
-        let map_range = MapRange::from(line);
+        let map_range = MapRange::from(line).unwrap();
 
         assert!(map_range.contains(&num::BigInt::from(77)));
         assert!(map_range.contains(&num::BigInt::from(99)));
@@ -264,21 +261,22 @@ mod test {
     #[test]
     fn ch05_map_range_map_to() {
         let line = "45 77 23";
-        let map_range = MapRange::from(line);
+        let map_range = MapRange::from(line).unwrap();
 
         assert_eq!(
             map_range.map_to(&num::BigInt::from(78)),
-            num::BigInt::from(46)
+            Some(num::BigInt::from(46))
         );
+        assert_eq!(map_range.map_to(&num::BigInt::from(100)), None);
     }
 
     #[test]
     fn ch05_map_range_from_end_huge_numbers() {
         let line = "45 18446744073709551615 5";
-        let map_range = MapRange::from(line);
+        let map_range = MapRange::from(line).unwrap();
 
         assert_eq!(
-            map_range.from_end(),
+            map_range.get_from_end(),
             "18446744073709551620".parse::<num::BigInt>().unwrap()
         );
     }
@@ -292,12 +290,12 @@ mod test {
             "39 0 15".into(),
         ];
 
-        let resource_map = ResourceMap::new(lines);
+        let resource_map = ResourceMap::new(lines).unwrap();
 
         let expected_ranges = vec![
-            MapRange::from("0 15 37"),
-            MapRange::from("37 52 2"),
-            MapRange::from("39 0 15"),
+            MapRange::from("0 15 37").unwrap(),
+            MapRange::from("37 52 2").unwrap(),
+            MapRange::from("39 0 15").unwrap(),
         ];
 
         assert_eq!(resource_map.from, Resource::Soil);
@@ -320,12 +318,12 @@ mod test {
             "39 0 15".into(),
         ];
 
-        let previous = ResourceMap::new(lines_1);
-        let mut current = ResourceMap::new(lines_2);
+        let previous = ResourceMap::new(lines_1).unwrap();
+        let mut current = ResourceMap::new(lines_2).unwrap();
 
         current.normalize(&previous);
 
-        let mut expected = vec![
+        let mut expected = [
             MapRange {
                 from_start: num::BigInt::from(0),
                 to_start: num::BigInt::from(39),
@@ -338,22 +336,24 @@ mod test {
             },
             MapRange {
                 from_start: num::BigInt::from(50),
-                to_start: num::BigInt::from(35),
-                length: num::BigInt::from(2),
-            },
-            MapRange {
-                from_start: num::BigInt::from(52),
                 to_start: num::BigInt::from(37),
                 length: num::BigInt::from(2),
             },
             MapRange {
-                from_start: num::BigInt::from(54),
+                from_start: num::BigInt::from(52),
                 to_start: num::BigInt::from(54),
                 length: num::BigInt::from(46),
             },
+            MapRange {
+                from_start: num::BigInt::from(98),
+                to_start: num::BigInt::from(35),
+                length: num::BigInt::from(2),
+            },
         ];
 
-        assert_eq!(current.ranges.sort(), expected.sort());
+        current.ranges.sort();
+        expected.sort();
+        assert_eq!(current.ranges, expected);
     }
 
     #[test]
@@ -371,12 +371,12 @@ mod test {
             "39 0 15".into(),
         ];
 
-        let previous = ResourceMap::new(lines_1);
-        let mut current = ResourceMap::new(lines_2);
+        let previous = ResourceMap::new(lines_1).unwrap();
+        let mut current = ResourceMap::new(lines_2).unwrap();
 
         current.merge_maps(&previous);
 
-        let mut expected = vec![
+        let mut expected = [
             MapRange {
                 from_start: num::BigInt::from(0),
                 to_start: num::BigInt::from(39),
@@ -404,6 +404,42 @@ mod test {
             },
         ];
 
-        assert_eq!(current.ranges.sort(), expected.sort());
+        current.ranges.sort();
+        expected.sort();
+        assert_eq!(current.ranges, expected);
+    }
+
+    #[test]
+    fn ch05_map_range_from_bad_value() {
+        let error = MapRange::from("45 7x 23").unwrap_err();
+        assert_eq!(
+            error,
+            ChallengeError::parse("7x", 4, "Could not parse range value")
+        );
+    }
+
+    #[test]
+    fn ch05_resource_map_new_bad_resource() {
+        let lines = vec!["soil-to-fertiliser map:".into(), "0 15 37".into()];
+        let error = ResourceMap::new(lines).unwrap_err();
+        assert_eq!(
+            error,
+            ChallengeError::parse("fertiliser", 9, "Attempted to parse an invalid resource")
+                .at_line(1)
+        );
+    }
+
+    #[test]
+    fn ch05_resource_map_new_reports_range_line() {
+        let lines = vec![
+            "soil-to-fertilizer map:".into(),
+            "0 15 37".into(),
+            "37 52".into(),
+        ];
+        let error = ResourceMap::new(lines).unwrap_err();
+        assert_eq!(
+            error,
+            ChallengeError::parse("37 52", 1, "Expected three range values").at_line(3)
+        );
     }
 }
@@ -1,18 +1,32 @@
+use crate::{error::ChallengeError, utils::split_offsets};
+
 pub struct History {
     extrapolated_rows: Vec<Vec<i32>>,
 }
 
 impl History {
-    pub fn new(line: &String) -> Self {
-        let sequence = line
-            .split(' ')
-            .map(|v| v.parse::<i32>().expect("Could not convert to number."))
-            .collect();
-        let extrapolated_rows = History::extrapolate_rows(sequence);
-        History { extrapolated_rows }
+    pub fn new(line: &str) -> Result<Self, ChallengeError> {
+        let sequence = split_offsets(line, ' ')
+            .filter(|(_, v)| !v.is_empty())
+            .map(|(offset, v)| {
+                v.parse::<i32>().map_err(|_| {
+                    ChallengeError::parse(v, offset + 1, "Could not convert to number.")
+                })
+            })
+            .collect::<Result<Vec<i32>, ChallengeError>>()?;
+        if sequence.is_empty() {
+            return Err(ChallengeError::parse(
+                line,
+                1,
+                "Expected at least one value.",
+            ));
+        }
+        let extrapolated_rows = History::extrapolate_rows(sequence)
+            .ok_or_else(|| ChallengeError::parse(line, 1, "Differences are too large."))?;
+        Ok(History { extrapolated_rows })
     }
 
-    fn extrapolate_rows(sequence: Vec<i32>) -> Vec<Vec<i32>> {
+    fn extrapolate_rows(sequence: Vec<i32>) -> Option<Vec<Vec<i32>>> {
         let mut rows = Vec::new();
         let mut next_row: Vec<i32> = sequence;
         rows.push(next_row.clone());
@@ -22,31 +36,38 @@ impl History {
                 .iter()
                 .skip(1)
                 .zip(next_row.iter())
-                .map(|(p, c)| p - c)
-                .collect();
+                .map(|(p, c)| p.checked_sub(*c))
+                .collect::<Option<Vec<i32>>>()?;
             rows.push(next_row.clone());
             if next_row.iter().all(|v| v.eq(&0)) {
                 break;
             }
         }
 
-        rows
+        Some(rows)
     }
 
-    pub fn extrapolate_next(&self) -> i32 {
+    // The sequence followed by each row of differences, ending in zeroes.
+    pub fn rows(&self) -> &[Vec<i32>] {
+        &self.extrapolated_rows
+    }
+
+    pub fn extrapolate_next(&self) -> Result<i32, ChallengeError> {
         self.extrapolated_rows
             .iter()
             .rev()
-            .map(|row| row.last().expect("No last element in row found."))
-            .fold(0, |acc, cur| acc + cur)
+            .map(|row| row.last().unwrap_or(&0))
+            .try_fold(0i32, |acc, cur| acc.checked_add(*cur))
+            .ok_or_else(|| ChallengeError::solve("Extrapolated value is too large."))
     }
 
-    pub fn extrapolate_prev(&self) -> i32 {
+    pub fn extrapolate_prev(&self) -> Result<i32, ChallengeError> {
         self.extrapolated_rows
             .iter()
             .rev()
-            .map(|row| row.first().expect("No first element found in row."))
-            .fold(0, |acc, cur| cur - acc)
+            .map(|row| row.first().unwrap_or(&0))
+            .try_fold(0i32, |acc, cur| cur.checked_sub(acc))
+            .ok_or_else(|| ChallengeError::solve("Extrapolated value is too large."))
     }
 }
 
@@ -56,19 +77,35 @@ mod test {
 
     #[test]
     fn ch09_history_extrapolate_next() {
-        let history = History::new(&String::from("3 6 9 12 15"));
-        assert_eq!(history.extrapolate_next(), 18);
+        let history = History::new("3 6 9 12 15").unwrap();
+        assert_eq!(history.extrapolate_next().unwrap(), 18);
     }
 
     #[test]
     fn ch09_history_extrapolate_next_ascending() {
-        let history = History::new(&String::from("1 3 6 10 15 21"));
-        assert_eq!(history.extrapolate_next(), 28);
+        let history = History::new("1 3 6 10 15 21").unwrap();
+        assert_eq!(history.extrapolate_next().unwrap(), 28);
     }
 
     #[test]
     fn ch09_history_extrapolate_prev() {
-        let history = History::new(&String::from("10 13 16 21 30 45"));
-        assert_eq!(history.extrapolate_prev(), 5);
+        let history = History::new("10 13 16 21 30 45").unwrap();
+        assert_eq!(history.extrapolate_prev().unwrap(), 5);
+    }
+
+    #[test]
+    fn ch09_history_new_bad_number() {
+        let error = History::new("10 13 l6 21").err().unwrap();
+        assert_eq!(
+            error,
+            ChallengeError::parse("l6", 7, "Could not convert to number.")
+        );
+    }
+
+    #[test]
+    fn ch09_history_never_constant() {
+        let history = History::new("1 2 4 8").unwrap();
+        assert_eq!(history.extrapolate_next().unwrap(), 15);
+        assert_eq!(history.extrapolate_prev().unwrap(), 0);
     }
 }
@@ -1,70 +1,147 @@
-use crate::{challenge::mirage_maintenance::history::History, utils::to_lines_vec};
+use crate::{answer::Answer, context::SolveContext, error::ChallengeError, utils::to_lines_vec};
 
-use super::Challenge;
-use std::fs::File;
+pub use self::history::History;
+
+use super::{Challenge, Registration};
+use std::io::BufRead;
 
 mod history;
 
+pub const REGISTRATION: Registration = Registration {
+    year: 2023,
+    day: 9,
+    title: "Mirage Maintenance",
+    version: 1,
+    create: || Box::new(MirageMaintenance::default()),
+};
+
 #[derive(Default)]
 pub struct MirageMaintenance {
-    lines: Vec<String>,
+    histories: Vec<History>,
 }
 
 impl Challenge for MirageMaintenance {
-    fn load(&mut self, file: &File) {
-        self.lines = to_lines_vec(file);
+    fn load(&mut self, reader: &mut dyn BufRead) -> Result<(), ChallengeError> {
+        self.load_lines(to_lines_vec(reader)?)
     }
-    fn solve_part_one(&self) -> String {
-        let histories_sum: i32 = self
-            .lines
-            .iter()
-            .map(|line| History::new(line))
-            .map(|history| history.extrapolate_next())
-            .sum();
+    fn solve_part_one(&self, context: &SolveContext) -> Result<Answer, ChallengeError> {
+        let histories_sum = self.sum_histories(context, History::extrapolate_next)?;
+
+        Ok(Answer::from(histories_sum))
+    }
+    fn solve_part_two(&self, context: &SolveContext) -> Result<Answer, ChallengeError> {
+        let histories_sum = self.sum_histories(context, History::extrapolate_prev)?;
+
+        Ok(Answer::from(histories_sum))
+    }
+    fn parse_line(&self, line: &str) -> Option<Result<String, ChallengeError>> {
+        Some(History::new(line).and_then(|history| {
+            Ok(format!(
+                "Previous: {}, next: {}",
+                history.extrapolate_prev()?,
+                history.extrapolate_next()?
+            ))
+        }))
+    }
+}
 
-        format!("{}", histories_sum)
+impl MirageMaintenance {
+    fn load_lines(&mut self, lines: Vec<String>) -> Result<(), ChallengeError> {
+        self.histories = lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| History::new(line).map_err(|e| e.at_line(i + 1)))
+            .collect::<Result<Vec<History>, ChallengeError>>()?;
+        Ok(())
     }
-    fn solve_part_two(&self) -> String {
-        let histories_sum: i32 = self
-            .lines
+
+    fn sum_histories(
+        &self,
+        context: &SolveContext,
+        extrapolate: impl Fn(&History) -> Result<i32, ChallengeError>,
+    ) -> Result<i32, ChallengeError> {
+        self.histories
             .iter()
-            .map(|line| History::new(line))
-            .map(|history| history.extrapolate_prev())
-            .sum();
+            .enumerate()
+            .try_fold(0i32, |acc, (i, history)| {
+                let value = extrapolate(history)?;
+                MirageMaintenance::explain_history(context, i + 1, history, value);
+                acc.checked_add(value)
+                    .ok_or_else(|| ChallengeError::solve("Sum of histories is too large."))
+            })
+    }
 
-        format!("{}", histories_sum)
+    fn explain_history(context: &SolveContext, number: usize, history: &History, value: i32) {
+        if !context.explain().enabled() {
+            return;
+        }
+
+        context
+            .explain()
+            .step(format_args!("History {} -> {}", number, value));
+        for (depth, row) in history.rows().iter().enumerate() {
+            let values: Vec<String> = row.iter().map(i32::to_string).collect();
+            context.explain().step(format_args!(
+                "{}{}",
+                " ".repeat(2 + depth),
+                values.join(" ")
+            ));
+        }
     }
 }
 
 #[cfg(test)]
 mod test {
+    use std::rc::Rc;
+
     use super::*;
 
     #[test]
     fn ch09_mirage_maintenance_part_one() {
-        let lines = vec![
-            String::from("0 3 6 9 12 15"),
-            String::from("1 3 6 10 15 21"),
-            String::from("10 13 16 21 30 45"),
-        ];
+        let lines = ["0 3 6 9 12 15", "1 3 6 10 15 21", "10 13 16 21 30 45"];
 
         let mut mirage_maintenance = MirageMaintenance::default();
-        mirage_maintenance.lines = lines;
+        mirage_maintenance.load_str(&lines.join("\n")).unwrap();
 
-        assert_eq!(mirage_maintenance.solve_part_one(), "114");
+        assert_eq!(mirage_maintenance.solve_part_one(&SolveContext::default()).unwrap(), "114");
     }
 
     #[test]
     fn ch09_mirage_maintenance_part_two() {
-        let lines = vec![
-            String::from("0 3 6 9 12 15"),
-            String::from("1 3 6 10 15 21"),
-            String::from("10 13 16 21 30 45"),
-        ];
+        let lines = ["0 3 6 9 12 15", "1 3 6 10 15 21", "10 13 16 21 30 45"];
+
+        let mut mirage_maintenance = MirageMaintenance::default();
+        mirage_maintenance.load_str(&lines.join("\n")).unwrap();
+
+        assert_eq!(mirage_maintenance.solve_part_two(&SolveContext::default()).unwrap(), "2");
+    }
 
+    #[test]
+    fn ch09_mirage_maintenance_explains_difference_pyramid() {
         let mut mirage_maintenance = MirageMaintenance::default();
-        mirage_maintenance.lines = lines;
+        mirage_maintenance.load_str("0 3 6 9 12 15").unwrap();
+        let (explain, captured) = crate::explain::test::captured();
+        let context = SolveContext::default().with_explain(Rc::new(explain));
+
+        mirage_maintenance.solve_part_one(&context).unwrap();
+        assert_eq!(
+            captured.contents(),
+            "  History 1 -> 18\n    0 3 6 9 12 15\n     3 3 3 3 3\n      0 0 0 0\n"
+        );
+    }
 
-        assert_eq!(mirage_maintenance.solve_part_two(), "2");
+    #[test]
+    fn ch09_mirage_maintenance_load_reports_line() {
+        let lines = ["0 3 6 9 12 15", "", "1 3 6 10 15 21"];
+
+        let mut mirage_maintenance = MirageMaintenance::default();
+        let error = mirage_maintenance
+            .load_str(&lines.join("\n"))
+            .err()
+            .unwrap();
+        assert_eq!(
+            error,
+            ChallengeError::parse("", 1, "Expected at least one value.").at_line(2)
+        );
     }
 }
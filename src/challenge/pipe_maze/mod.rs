@@ -1,24 +1,36 @@
-use crate::utils::to_lines_vec;
+use crate::{answer::Answer, context::SolveContext, error::ChallengeError, utils::to_lines_vec};
 
-use self::pipe_map::PipeMap;
+pub use self::{
+    pipe::Pipe,
+    pipe_map::{PipeMap, PipeNetworkIterator},
+    pipe_type::PipeType,
+};
 
-use super::Challenge;
-use std::fs::File;
+use super::{Challenge, Registration};
+use std::io::BufRead;
 
 mod pipe;
 mod pipe_map;
 mod pipe_type;
 
+pub const REGISTRATION: Registration = Registration {
+    year: 2023,
+    day: 10,
+    title: "Pipe Maze",
+    version: 1,
+    create: || Box::new(PipeMaze::default()),
+};
+
 #[derive(Default)]
 pub struct PipeMaze {
     pipe_map: PipeMap,
 }
 
 impl Challenge for PipeMaze {
-    fn load(&mut self, file: &File) {
-        self.load_map(to_lines_vec(file));
+    fn load(&mut self, reader: &mut dyn BufRead) -> Result<(), ChallengeError> {
+        self.load_map(to_lines_vec(reader)?)
     }
-    fn solve_part_one(&self) -> String {
+    fn solve_part_one(&self, context: &SolveContext) -> Result<Answer, ChallengeError> {
         let furthest = self
             .pipe_map
             .into_iter()
@@ -27,53 +39,74 @@ impl Challenge for PipeMaze {
             .filter(|((_, p1), (_, p2))| p1 == p2)
             .map(|((i1, _), (_, _))| i1)
             .max()
-            .unwrap();
-        format!("{}", furthest)
+            .ok_or_else(|| ChallengeError::solve("Pipe network is empty."))?;
+        if context.explain().enabled() {
+            let (row, col) = self.pipe_map.start();
+            context.explain().step(format_args!(
+                "Loop from S at line {}, column {} is {} pipes long",
+                row + 1,
+                col + 1,
+                self.pipe_map.into_iter().count()
+            ));
+            context.explain().step(format_args!(
+                "Furthest pipe is {} steps away in both directions",
+                furthest
+            ));
+        }
+        Ok(Answer::from(furthest))
     }
-    fn solve_part_two(&self) -> String {
-        format!("Not implemented yet!")
+    fn solve_part_two(&self, _context: &SolveContext) -> Result<Answer, ChallengeError> {
+        Ok(Answer::NotImplemented)
     }
 }
 
 impl PipeMaze {
-    pub fn load_map(&mut self, lines: Vec<String>) {
-        self.pipe_map = PipeMap::new(lines);
+    fn load_map(&mut self, lines: Vec<String>) -> Result<(), ChallengeError> {
+        self.pipe_map = PipeMap::new(lines)?;
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod test {
+    use std::rc::Rc;
+
     use super::*;
 
     #[test]
     fn ch10_pipe_maze_part_one_example_one() {
-        let lines = vec![
-            String::from("....."),
-            String::from(".S-7."),
-            String::from(".|.|."),
-            String::from(".L-J."),
-            String::from("....."),
-        ];
+        let lines = [".....", ".S-7.", ".|.|.", ".L-J.", "....."];
 
         let mut pipe_maze = PipeMaze::default();
-        pipe_maze.load_map(lines);
+        pipe_maze.load_str(&lines.join("\n")).unwrap();
 
-        assert_eq!(pipe_maze.solve_part_one(), "4");
+        assert_eq!(pipe_maze.solve_part_one(&SolveContext::default()).unwrap(), "4");
     }
 
     #[test]
     fn ch10_pipe_maze_part_one_example_two() {
-        let lines = vec![
-            String::from("..F7."),
-            String::from(".FJ|."),
-            String::from("SJ.L7"),
-            String::from("|F--J"),
-            String::from("LJ..."),
-        ];
+        let lines = ["..F7.", ".FJ|.", "SJ.L7", "|F--J", "LJ..."];
+
+        let mut pipe_maze = PipeMaze::default();
+        pipe_maze.load_str(&lines.join("\n")).unwrap();
+
+        assert_eq!(pipe_maze.solve_part_one(&SolveContext::default()).unwrap(), "8");
+    }
+
+    #[test]
+    fn ch10_pipe_maze_part_one_explains_loop_length() {
+        let lines = [".....", ".S-7.", ".|.|.", ".L-J.", "....."];
 
         let mut pipe_maze = PipeMaze::default();
-        pipe_maze.load_map(lines);
+        pipe_maze.load_str(&lines.join("\n")).unwrap();
+        let (explain, captured) = crate::explain::test::captured();
+        let context = SolveContext::default().with_explain(Rc::new(explain));
 
-        assert_eq!(pipe_maze.solve_part_one(), "8");
+        pipe_maze.solve_part_one(&context).unwrap();
+        assert_eq!(
+            captured.contents(),
+            "  Loop from S at line 2, column 2 is 8 pipes long\n  \
+             Furthest pipe is 4 steps away in both directions\n"
+        );
     }
 }
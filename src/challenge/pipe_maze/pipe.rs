@@ -1,4 +1,5 @@
 use super::pipe_type::PipeType;
+use crate::error::ChallengeError;
 
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Pipe {
@@ -7,11 +8,12 @@ pub struct Pipe {
 }
 
 impl Pipe {
-    pub fn new(c: char, position: (usize, usize)) -> Self {
-        let pipe_type = PipeType::from(c);
-        Pipe {
+    pub fn new(c: char, position: (usize, usize)) -> Result<Self, ChallengeError> {
+        let pipe_type =
+            PipeType::from(c).map_err(|e| e.offset_columns(position.1).at_line(position.0 + 1))?;
+        Ok(Pipe {
             position,
             pipe_type,
-        }
+        })
     }
 }
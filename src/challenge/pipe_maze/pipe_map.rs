@@ -1,71 +1,57 @@
 use std::collections::HashSet;
 
 use super::{pipe::Pipe, pipe_type::PipeType};
+use crate::{error::ChallengeError, utils::Grid};
 
 #[derive(Default)]
 pub struct PipeMap {
-    map: Vec<Vec<Pipe>>,
+    map: Grid<Pipe>,
     start: (usize, usize),
 }
 
 impl PipeMap {
-    pub fn new(lines: Vec<String>) -> Self {
-        let mut start: Option<(usize, usize)> = None;
-        let map = lines
-            .into_iter()
-            .enumerate()
-            .map(|(line_index, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(|(c_index, c)| {
-                        let pipe = Pipe::new(c, (line_index, c_index));
-                        if pipe.pipe_type == PipeType::Start {
-                            start = Some((line_index, c_index));
-                        }
-                        pipe
-                    })
-                    .collect::<Vec<Pipe>>()
-            })
-            .collect::<Vec<Vec<Pipe>>>();
+    pub fn new(lines: Vec<String>) -> Result<Self, ChallengeError> {
+        let map = Grid::parse(&lines, Pipe::new)?;
+        let start = map
+            .cells()
+            .find(|(_, pipe)| pipe.pipe_type == PipeType::Start)
+            .map(|(position, _)| position);
 
-        let start = start.expect("No start position found in pipe map.");
+        let start =
+            start.ok_or_else(|| ChallengeError::solve("No start position found in pipe map."))?;
 
-        PipeMap { map, start }
+        let pipe_map = PipeMap { map, start };
+        pipe_map.validate_network()?;
+
+        Ok(pipe_map)
     }
 
-    pub fn get_connected(&self, pipe: &Pipe) -> Vec<&Pipe> {
-        let (row, col) = pipe.position;
+    fn validate_network(&self) -> Result<(), ChallengeError> {
+        let mut visited = HashSet::from([self.start]);
+        let mut pending = vec![self.at(self.start.0, self.start.1)];
+        while let Some(pipe) = pending.pop() {
+            for next in self.get_connected(pipe)? {
+                if visited.insert(next.position) {
+                    pending.push(next);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn neighbor(&self, pipe: &Pipe, offset: (isize, isize)) -> Option<&Pipe> {
+        self.map.get(self.map.offset(pipe.position, offset)?)
+    }
+
+    pub fn get_connected(&self, pipe: &Pipe) -> Result<Vec<&Pipe>, ChallengeError> {
         let mut pipe_type = pipe.pipe_type;
 
         if let PipeType::Start = pipe_type {
-            let up: Option<PipeType> = {
-                if row != 0 {
-                    Some(self.map[row - 1][col].pipe_type)
-                } else {
-                    None
-                }
-            };
-            let right: Option<PipeType> = {
-                if col < self.map[0].len() - 1 {
-                    Some(self.map[row][col + 1].pipe_type)
-                } else {
-                    None
-                }
-            };
-            let down: Option<PipeType> = {
-                if row < self.map.len() - 1 {
-                    Some(self.map[row + 1][col].pipe_type)
-                } else {
-                    None
-                }
-            };
-            let left: Option<PipeType> = {
-                if col != 0 {
-                    Some(self.map[row][col - 1].pipe_type)
-                } else {
-                    None
-                }
-            };
+            let up = self.neighbor(pipe, (-1, 0)).map(|p| p.pipe_type);
+            let right = self.neighbor(pipe, (0, 1)).map(|p| p.pipe_type);
+            let down = self.neighbor(pipe, (1, 0)).map(|p| p.pipe_type);
+            let left = self.neighbor(pipe, (0, -1)).map(|p| p.pipe_type);
 
             pipe_type = match (up, right, down, left) {
                 (
@@ -104,66 +90,49 @@ impl PipeMap {
                     Some(PipeType::ElbowNE | PipeType::ElbowNW | PipeType::Vertical),
                     Some(PipeType::Horizontal | PipeType::ElbowNE | PipeType::ElbowSE),
                 ) => PipeType::ElbowSW,
-                (_, _, _, _) => panic!("Could not determine start position pipe type."),
+                (_, _, _, _) => {
+                    return Err(ChallengeError::solve(
+                        "Could not determine start position pipe type.",
+                    ))
+                }
             }
         }
 
-        match pipe_type {
-            PipeType::Vertical => {
-                if row == 0 || row >= self.map.len() - 1 {
-                    panic!("Pipe network hit vertical edge of map at row {}.", row);
-                }
-                vec![&self.map[row - 1][col], &self.map[row + 1][col]]
-            }
-            PipeType::Horizontal => {
-                if col == 0 || col >= self.map[0].len() - 1 {
-                    panic!("Pipe network hit horizontal edge of map at column {}.", col);
-                }
-                vec![&self.map[row][col - 1], &self.map[row][col + 1]]
-            }
-            PipeType::ElbowNE => {
-                if row == 0 || col >= self.map[0].len() - 1 {
-                    panic!(
-                        "Pipe network hit edge of map at position ({}, {})",
-                        row, col
-                    );
-                }
-                vec![&self.map[row - 1][col], &self.map[row][col + 1]]
-            }
-            PipeType::ElbowNW => {
-                if row == 0 || col == 0 {
-                    panic!(
-                        "Pipe network hit edge of map at position ({}, {})",
-                        row, col
-                    );
-                }
-                vec![&self.map[row - 1][col], &self.map[row][col - 1]]
-            }
-            PipeType::ElbowSW => {
-                if row >= self.map.len() || col == 0 {
-                    panic!(
-                        "Pipe network hit edge of map at position ({}, {})",
-                        row, col
-                    );
-                }
-                vec![&self.map[row + 1][col], &self.map[row][col - 1]]
+        let offsets = match pipe_type {
+            PipeType::Vertical => [(-1, 0), (1, 0)],
+            PipeType::Horizontal => [(0, -1), (0, 1)],
+            PipeType::ElbowNE => [(-1, 0), (0, 1)],
+            PipeType::ElbowNW => [(-1, 0), (0, -1)],
+            PipeType::ElbowSW => [(1, 0), (0, -1)],
+            PipeType::ElbowSE => [(1, 0), (0, 1)],
+            PipeType::Start => return Err(ChallengeError::solve("Start pipe not converted.")),
+            PipeType::Ground => {
+                return Err(ChallengeError::solve(&format!(
+                    "Can't navigate pipe network from ground tile at position {:?}.",
+                    pipe.position
+                )))
             }
-            PipeType::ElbowSE => {
-                if row >= self.map.len() - 1 || col >= self.map[0].len() - 1 {
-                    panic!(
-                        "Pipe network hit edge of map at position ({}, {})",
-                        row, col
-                    );
-                }
-                vec![&self.map[row + 1][col], &self.map[row][col + 1]]
-            }
-            PipeType::Start => panic!("Start pipe not converted."),
-            PipeType::Ground => panic!("Can't navigate pipe network from ground tile."),
-        }
+        };
+
+        offsets
+            .into_iter()
+            .map(|offset| {
+                self.neighbor(pipe, offset).ok_or_else(|| {
+                    ChallengeError::solve(&format!(
+                        "Pipe network hit edge of map at position {:?}.",
+                        pipe.position
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    pub fn start(&self) -> (usize, usize) {
+        self.start
     }
 
     pub fn at(&self, row: usize, col: usize) -> &Pipe {
-        &self.map[row][col]
+        &self.map[(row, col)]
     }
 }
 
@@ -198,49 +167,33 @@ impl<'a> Iterator for PipeNetworkIterator<'a> {
     type Item = &'a Pipe;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if let None = self.current {
-            return None;
-        }
-        let current = self.current.unwrap();
-        let (cur_row, cur_col) = current;
+        let (cur_row, cur_col) = self.current?;
         self.visited.insert((cur_row, cur_col));
         let cur_pipe = self.pipe_map.at(cur_row, cur_col);
-        let mut connected = self.pipe_map.get_connected(cur_pipe);
+        let mut connected = self.pipe_map.get_connected(cur_pipe).unwrap_or_default();
         connected.sort();
         let next_pipe = connected
             .into_iter()
-            .skip_while(|pipe| self.visited.contains(&pipe.position))
-            .next();
-        self.current = match next_pipe {
-            Some(pipe) => Some(pipe.position),
-            None => None,
-        };
+            .find(|pipe| !self.visited.contains(&pipe.position));
+        self.current = next_pipe.map(|pipe| pipe.position);
 
-        return Some(cur_pipe);
+        Some(cur_pipe)
     }
 }
 
 impl<'a> DoubleEndedIterator for PipeNetworkIterator<'a> {
     fn next_back(&mut self) -> Option<Self::Item> {
-        if let None = self.current {
-            return None;
-        }
-        let current = self.current.unwrap();
-        let (cur_row, cur_col) = current;
+        let (cur_row, cur_col) = self.current?;
         self.visited.insert((cur_row, cur_col));
         let cur_pipe = self.pipe_map.at(cur_row, cur_col);
-        let mut connected = self.pipe_map.get_connected(cur_pipe);
+        let mut connected = self.pipe_map.get_connected(cur_pipe).unwrap_or_default();
         connected.sort_by(|a, b| b.cmp(a));
         let next_pipe = connected
             .into_iter()
-            .skip_while(|pipe| self.visited.contains(&pipe.position))
-            .next();
-        self.current = match next_pipe {
-            Some(pipe) => Some(pipe.position),
-            None => None,
-        };
+            .find(|pipe| !self.visited.contains(&pipe.position));
+        self.current = next_pipe.map(|pipe| pipe.position);
 
-        return Some(cur_pipe);
+        Some(cur_pipe)
     }
 }
 
@@ -252,25 +205,31 @@ mod test {
     fn ch10_pipe_map_new() {
         let lines = vec![String::from("S7"), String::from("LJ")];
 
-        let pipe_map = PipeMap::new(lines);
+        let pipe_map = PipeMap::new(lines).unwrap();
 
         let expected = vec![
-            vec![Pipe::new('S', (0, 0)), Pipe::new('7', (0, 1))],
-            vec![Pipe::new('L', (1, 0)), Pipe::new('J', (1, 1))],
+            vec![
+                Pipe::new('S', (0, 0)).unwrap(),
+                Pipe::new('7', (0, 1)).unwrap(),
+            ],
+            vec![
+                Pipe::new('L', (1, 0)).unwrap(),
+                Pipe::new('J', (1, 1)).unwrap(),
+            ],
         ];
 
-        assert_eq!(pipe_map.map, expected);
+        assert_eq!(pipe_map.map, Grid::new(expected).unwrap());
     }
 
     #[test]
     fn ch10_pipe_map_get_connected() {
         let lines = vec![String::from("S7"), String::from("LJ")];
-        let pipe_map = PipeMap::new(lines);
+        let pipe_map = PipeMap::new(lines).unwrap();
 
-        let pipe_ref = &pipe_map.map[1][1];
+        let pipe_ref = &pipe_map.map[(1, 1)];
 
-        let mut connected = pipe_map.get_connected(pipe_ref);
-        let mut expected = vec![&pipe_map.map[1][0], &pipe_map.map[0][1]];
+        let mut connected = pipe_map.get_connected(pipe_ref).unwrap();
+        let mut expected = vec![&pipe_map.map[(1, 0)], &pipe_map.map[(0, 1)]];
 
         connected.sort();
         expected.sort();
@@ -281,26 +240,55 @@ mod test {
     #[test]
     fn ch10_pipe_map_iterator() {
         let lines = vec![String::from("S7"), String::from("LJ")];
-        let pipe_map = PipeMap::new(lines);
+        let pipe_map = PipeMap::new(lines).unwrap();
         let mut pipe_iter = pipe_map.into_iter();
 
-        assert_eq!(pipe_iter.next().unwrap(), &pipe_map.map[0][0]);
-        assert_eq!(pipe_iter.next().unwrap(), &pipe_map.map[0][1]);
-        assert_eq!(pipe_iter.next().unwrap(), &pipe_map.map[1][1]);
-        assert_eq!(pipe_iter.next().unwrap(), &pipe_map.map[1][0]);
+        assert_eq!(pipe_iter.next().unwrap(), &pipe_map.map[(0, 0)]);
+        assert_eq!(pipe_iter.next().unwrap(), &pipe_map.map[(0, 1)]);
+        assert_eq!(pipe_iter.next().unwrap(), &pipe_map.map[(1, 1)]);
+        assert_eq!(pipe_iter.next().unwrap(), &pipe_map.map[(1, 0)]);
         assert!(pipe_iter.next().is_none());
     }
 
     #[test]
     fn ch10_pipe_map_iterator_rev() {
         let lines = vec![String::from("S7"), String::from("LJ")];
-        let pipe_map = PipeMap::new(lines);
+        let pipe_map = PipeMap::new(lines).unwrap();
         let mut pipe_iter = pipe_map.into_iter().rev();
 
-        assert_eq!(pipe_iter.next().unwrap(), &pipe_map.map[0][0]);
-        assert_eq!(pipe_iter.next().unwrap(), &pipe_map.map[1][0]);
-        assert_eq!(pipe_iter.next().unwrap(), &pipe_map.map[1][1]);
-        assert_eq!(pipe_iter.next().unwrap(), &pipe_map.map[0][1]);
+        assert_eq!(pipe_iter.next().unwrap(), &pipe_map.map[(0, 0)]);
+        assert_eq!(pipe_iter.next().unwrap(), &pipe_map.map[(1, 0)]);
+        assert_eq!(pipe_iter.next().unwrap(), &pipe_map.map[(1, 1)]);
+        assert_eq!(pipe_iter.next().unwrap(), &pipe_map.map[(0, 1)]);
         assert!(pipe_iter.next().is_none());
     }
+
+    #[test]
+    fn ch10_pipe_map_new_invalid_tile() {
+        let lines = vec![String::from("S7"), String::from("LX")];
+
+        let error = PipeMap::new(lines).err().unwrap();
+        assert_eq!(
+            error,
+            ChallengeError::parse("X", 2, "Invalid pipe type").at_line(2)
+        );
+    }
+
+    #[test]
+    fn ch10_pipe_map_new_no_start() {
+        let lines = vec![String::from("F7"), String::from("LJ")];
+
+        assert!(PipeMap::new(lines).is_err());
+    }
+
+    #[test]
+    fn ch10_pipe_map_new_broken_network() {
+        let lines = vec![
+            String::from("S7."),
+            String::from("L-J"),
+            String::from("|.."),
+        ];
+
+        assert!(PipeMap::new(lines).is_err());
+    }
 }
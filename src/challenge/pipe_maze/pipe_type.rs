@@ -1,3 +1,5 @@
+use crate::error::ChallengeError;
+
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy)]
 pub enum PipeType {
     Vertical,
@@ -11,17 +13,21 @@ pub enum PipeType {
 }
 
 impl PipeType {
-    pub fn from(c: char) -> PipeType {
+    pub fn from(c: char) -> Result<PipeType, ChallengeError> {
         match c {
-            '|' => PipeType::Vertical,
-            '-' => PipeType::Horizontal,
-            'L' => PipeType::ElbowNE,
-            'J' => PipeType::ElbowNW,
-            '7' => PipeType::ElbowSW,
-            'F' => PipeType::ElbowSE,
-            '.' => PipeType::Ground,
-            'S' => PipeType::Start,
-            _ => panic!("Invalid pipe type {}", c),
+            '|' => Ok(PipeType::Vertical),
+            '-' => Ok(PipeType::Horizontal),
+            'L' => Ok(PipeType::ElbowNE),
+            'J' => Ok(PipeType::ElbowNW),
+            '7' => Ok(PipeType::ElbowSW),
+            'F' => Ok(PipeType::ElbowSE),
+            '.' => Ok(PipeType::Ground),
+            'S' => Ok(PipeType::Start),
+            _ => Err(ChallengeError::parse(
+                &c.to_string(),
+                1,
+                "Invalid pipe type",
+            )),
         }
     }
 }
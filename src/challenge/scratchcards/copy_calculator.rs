@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use super::{score_calculator::ScoreCalculator, scratchcard::Scratchcard};
 
+#[derive(Default)]
 pub struct CopyCalculator {
     copies: HashMap<u32, u32>,
 }
@@ -11,6 +12,11 @@ impl CopyCalculator {
         let copies = HashMap::new();
         CopyCalculator { copies }
     }
+
+    // Original plus won copies of a card, once the total has been calculated.
+    pub fn copies(&self, id: u32) -> u32 {
+        self.copies.get(&id).copied().unwrap_or(0)
+    }
 }
 
 impl ScoreCalculator for CopyCalculator {
@@ -19,10 +25,11 @@ impl ScoreCalculator for CopyCalculator {
         count
     }
 
-    fn calculate_total(&mut self, cards: &Vec<Scratchcard>) -> u32 {
+    fn calculate_total(&mut self, cards: &[Scratchcard]) -> u32 {
         cards.iter().for_each(|card| {
-            *self.copies.entry(card.id).or_insert(0) += 1;
-            let card_copies = self.copies.get(&card.id).unwrap().clone();
+            let card_copies = self.copies.entry(card.id).or_insert(0);
+            *card_copies += 1;
+            let card_copies = *card_copies;
             let score = CopyCalculator::calculate_card(card);
 
             for i in 1..=score {
@@ -30,7 +37,7 @@ impl ScoreCalculator for CopyCalculator {
             }
         });
 
-        self.copies.iter().map(|(_, copy_count)| copy_count).sum()
+        self.copies.values().sum()
     }
 }
 
@@ -42,19 +49,21 @@ mod test {
 
     #[test]
     fn ch04_copy_calculator_calculate_card() {
-        let card = Scratchcard::parse("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53");
+        let card = Scratchcard::parse("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53").unwrap();
         let score = CopyCalculator::calculate_card(&card);
         assert_eq!(score, 4);
     }
 
     #[test]
     fn ch04_copy_calculator_calculate_total() {
-        let card1 = Scratchcard::parse("Card 1: 10 11 12 13 14 | 10 11 17 18 19 20 21 22");
-        let card2 = Scratchcard::parse("Card 2: 10 11 12 13 14 | 10 16 17 18 19 20 21 22");
-        let card3 = Scratchcard::parse("Card 3: 10 11 12 13 14 | 15 16 17 18 19 20 21 22");
+        let card1 = Scratchcard::parse("Card 1: 10 11 12 13 14 | 10 11 17 18 19 20 21 22").unwrap();
+        let card2 = Scratchcard::parse("Card 2: 10 11 12 13 14 | 10 16 17 18 19 20 21 22").unwrap();
+        let card3 = Scratchcard::parse("Card 3: 10 11 12 13 14 | 15 16 17 18 19 20 21 22").unwrap();
 
-        let score = CopyCalculator::new().calculate_total(&vec![card1, card2, card3]);
+        let mut calculator = CopyCalculator::new();
+        let score = calculator.calculate_total(&[card1, card2, card3]);
 
         assert_eq!(score, 7);
+        assert_eq!(calculator.copies(3), 4);
     }
 }
@@ -1,72 +1,114 @@
-use crate::utils::to_lines_vec;
+use crate::{answer::Answer, context::SolveContext, error::ChallengeError, utils::to_lines_vec};
 
-use self::{
+pub use self::{
     copy_calculator::CopyCalculator, power_calculator::PowerCalculator,
     score_calculator::ScoreCalculator, scratchcard::Scratchcard,
 };
 
-use super::Challenge;
-use std::fs::File;
+use super::{Challenge, Registration};
+use std::io::BufRead;
 
 mod copy_calculator;
 mod power_calculator;
 mod score_calculator;
 mod scratchcard;
 
+pub const REGISTRATION: Registration = Registration {
+    year: 2023,
+    day: 4,
+    title: "Scratchcards",
+    version: 1,
+    create: || Box::new(Scratchcards::default()),
+};
+
 #[derive(Default)]
 pub struct Scratchcards {
     scratchcards: Vec<Scratchcard>,
 }
 
 impl Challenge for Scratchcards {
-    fn load(&mut self, file: &File) {
-        self.scratchcards = to_lines_vec(file)
+    fn load(&mut self, reader: &mut dyn BufRead) -> Result<(), ChallengeError> {
+        self.scratchcards = to_lines_vec(reader)?
             .into_iter()
-            .map(|line| Scratchcard::parse(&line))
-            .collect();
+            .enumerate()
+            .map(|(i, line)| Scratchcard::parse(&line).map_err(|e| e.at_line(i + 1)))
+            .collect::<Result<Vec<Scratchcard>, ChallengeError>>()?;
+        Ok(())
     }
-    fn solve_part_one(&self) -> String {
+    fn solve_part_one(&self, _context: &SolveContext) -> Result<Answer, ChallengeError> {
         let total = PowerCalculator::new().calculate_total(&self.scratchcards);
 
-        format!("{}", total)
+        Ok(Answer::from(total))
     }
-    fn solve_part_two(&self) -> String {
-        let total = CopyCalculator::new().calculate_total(&self.scratchcards);
+    fn solve_part_two(&self, context: &SolveContext) -> Result<Answer, ChallengeError> {
+        let mut calculator = CopyCalculator::new();
+        let total = calculator.calculate_total(&self.scratchcards);
+        if context.explain().enabled() {
+            for card in &self.scratchcards {
+                context.explain().step(format_args!(
+                    "Card {}: {} matches, {} copies",
+                    card.id,
+                    card.get_matches().len(),
+                    calculator.copies(card.id)
+                ));
+            }
+        }
 
-        format!("{}", total)
+        Ok(Answer::from(total))
+    }
+    fn parse_line(&self, line: &str) -> Option<Result<String, ChallengeError>> {
+        Some(Scratchcard::parse(line).map(|card| {
+            let matches: Vec<String> = card.get_matches().iter().map(|n| n.to_string()).collect();
+            format!(
+                "Card {}: {} matches ({})",
+                card.id,
+                matches.len(),
+                matches.join(", ")
+            )
+        }))
     }
 }
 
 #[cfg(test)]
 mod test {
+    use std::rc::Rc;
+
     use super::*;
 
     fn create_test_case() -> Scratchcards {
-        let lines = vec![
-            String::from("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53"),
-            String::from("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19"),
-            String::from("Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1"),
-            String::from("Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83"),
-            String::from("Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36"),
-            String::from("Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"),
-        ];
-        Scratchcards {
-            scratchcards: lines
-                .into_iter()
-                .map(|line| Scratchcard::parse(&line))
-                .collect(),
-        }
+        let input = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+        let mut scratchcards = Scratchcards::default();
+        scratchcards.load_str(input).unwrap();
+        scratchcards
     }
 
     #[test]
     fn ch04_scratchcards_part_one() {
         let scratchcards = create_test_case();
-        assert_eq!(scratchcards.solve_part_one(), "13");
+        assert_eq!(scratchcards.solve_part_one(&SolveContext::default()).unwrap(), "13");
     }
 
     #[test]
     fn ch04_scratchcards_part_two() {
         let scratchcards = create_test_case();
-        assert_eq!(scratchcards.solve_part_two(), "30");
+        assert_eq!(scratchcards.solve_part_two(&SolveContext::default()).unwrap(), "30");
+    }
+
+    #[test]
+    fn ch04_scratchcards_part_two_explains_copies() {
+        let scratchcards = create_test_case();
+        let (explain, captured) = crate::explain::test::captured();
+        let context = SolveContext::default().with_explain(Rc::new(explain));
+
+        scratchcards.solve_part_two(&context).unwrap();
+        let copies: Vec<String> = captured.contents().lines().map(String::from).collect();
+        assert_eq!(copies[0], "  Card 1: 4 matches, 1 copies");
+        assert_eq!(copies[4], "  Card 5: 0 matches, 14 copies");
     }
 }
@@ -1,5 +1,6 @@
 use super::score_calculator::ScoreCalculator;
 
+#[derive(Default)]
 pub struct PowerCalculator {}
 
 impl PowerCalculator {
@@ -15,13 +16,13 @@ impl ScoreCalculator for PowerCalculator {
             return 0;
         }
         let base: u32 = 2;
-        return base.pow(count - 1);
+        base.pow(count - 1)
     }
 
-    fn calculate_total(&mut self, scratchcards: &Vec<super::scratchcard::Scratchcard>) -> u32 {
+    fn calculate_total(&mut self, scratchcards: &[super::scratchcard::Scratchcard]) -> u32 {
         scratchcards
             .iter()
-            .map(|card| PowerCalculator::calculate_card(card))
+            .map(PowerCalculator::calculate_card)
             .sum()
     }
 }
@@ -34,18 +35,18 @@ mod test {
 
     #[test]
     fn ch04_power_calculator_calculate_card() {
-        let card = Scratchcard::parse("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53");
+        let card = Scratchcard::parse("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53").unwrap();
         let score = PowerCalculator::calculate_card(&card);
         assert_eq!(score, 8);
     }
 
     #[test]
     fn ch04_power_calculator_calculate_total() {
-        let card1 = Scratchcard::parse("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53");
-        let card2 = Scratchcard::parse("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19");
-        let card3 = Scratchcard::parse("Card 3: 87 83 26 28 32 | 88 30 70 12 93 22 82 36");
+        let card1 = Scratchcard::parse("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53").unwrap();
+        let card2 = Scratchcard::parse("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19").unwrap();
+        let card3 = Scratchcard::parse("Card 3: 87 83 26 28 32 | 88 30 70 12 93 22 82 36").unwrap();
 
-        let score = PowerCalculator::new().calculate_total(&vec![card1, card2, card3]);
+        let score = PowerCalculator::new().calculate_total(&[card1, card2, card3]);
         assert_eq!(score, 10);
     }
 }
@@ -1,5 +1,7 @@
 use std::collections::HashSet;
 
+use crate::{error::ChallengeError, utils::split_offsets};
+
 #[derive(Debug, PartialEq)]
 pub struct Scratchcard {
     pub id: u32,
@@ -8,43 +10,53 @@ pub struct Scratchcard {
 }
 
 impl Scratchcard {
-    pub fn parse(line: &str) -> Self {
-        let line_err = format!("Error parsing line: {}", line);
-        let mut line_iter = line.split(':');
-        let id = line_iter
-            .next()
-            .expect(&line_err)
-            .split(' ')
-            .filter(|v| !v.is_empty())
-            .skip(1)
-            .next()
-            .expect(&line_err)
-            .parse::<u32>()
-            .expect(&line_err);
-
-        let mut num_iter = line_iter.next().expect(&line_err).split('|');
+    pub fn parse(line: &str) -> Result<Self, ChallengeError> {
+        let (card_segment, numbers_segment) = line
+            .split_once(':')
+            .ok_or_else(|| ChallengeError::parse(line, 1, "Could not find ':' after card ID"))?;
+        let (id_offset, id_str) = split_offsets(card_segment, ' ')
+            .filter(|(_, v)| !v.is_empty())
+            .nth(1)
+            .ok_or_else(|| ChallengeError::parse(card_segment, 1, "Could not find card ID"))?;
+        let id = id_str.parse::<u32>().map_err(|_| {
+            ChallengeError::parse(id_str, id_offset + 1, "Could not parse card ID")
+        })?;
 
-        let winning_numbers: HashSet<u32> = num_iter
-            .next()
-            .expect(&line_err)
-            .split(' ')
-            .filter(|v| !v.is_empty())
-            .map(|v| v.trim().parse::<u32>().expect(&line_err))
-            .collect();
+        let numbers_offset = card_segment.len() + 1;
+        let (winning_segment, candidate_segment) =
+            numbers_segment.split_once('|').ok_or_else(|| {
+                ChallengeError::parse(
+                    numbers_segment,
+                    numbers_offset + 1,
+                    "Could not find '|' separator",
+                )
+            })?;
 
-        let candidate_numbers: Vec<u32> = num_iter
-            .next()
-            .expect(&line_err)
-            .split(' ')
-            .filter(|v| !v.is_empty())
-            .map(|v| v.trim().parse::<u32>().expect(&line_err))
-            .collect();
+        let winning_numbers = Scratchcard::parse_numbers(winning_segment, numbers_offset)?
+            .into_iter()
+            .collect::<HashSet<u32>>();
+        let candidate_numbers = Scratchcard::parse_numbers(
+            candidate_segment,
+            numbers_offset + winning_segment.len() + 1,
+        )?;
 
-        Scratchcard {
+        Ok(Scratchcard {
             id,
             winning_numbers,
             candidate_numbers,
-        }
+        })
+    }
+
+    // `segment_offset` is where the segment starts in its line.
+    fn parse_numbers(segment: &str, segment_offset: usize) -> Result<Vec<u32>, ChallengeError> {
+        split_offsets(segment, ' ')
+            .filter(|(_, v)| !v.is_empty())
+            .map(|(offset, v)| {
+                v.parse::<u32>().map_err(|_| {
+                    ChallengeError::parse(v, segment_offset + offset + 1, "Could not parse number")
+                })
+            })
+            .collect()
     }
 
     pub fn get_matches(&self) -> Vec<&u32> {
@@ -62,7 +74,7 @@ mod test {
     #[test]
     fn ch04_scratchcard_parse() {
         let test_card = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
-        let scratchcard = Scratchcard::parse(test_card);
+        let scratchcard = Scratchcard::parse(test_card).unwrap();
         let expected = Scratchcard {
             id: 1,
             winning_numbers: HashSet::from([41, 48, 83, 86, 17]),
@@ -74,7 +86,7 @@ mod test {
     #[test]
     fn ch04_scratchcard_parse_with_spaces() {
         let test_card = "Card     1: 41   48 83 86   17     | 83   86  6 31 17  9     48 53   ";
-        let scratchcard = Scratchcard::parse(test_card);
+        let scratchcard = Scratchcard::parse(test_card).unwrap();
         let expected = Scratchcard {
             id: 1,
             winning_numbers: HashSet::from([41, 48, 83, 86, 17]),
@@ -82,4 +94,34 @@ mod test {
         };
         assert_eq!(scratchcard, expected);
     }
+
+    #[test]
+    fn ch04_scratchcard_parse_bad_number() {
+        let test_card = "Card 1: 41 48 | 83 8b 6";
+        let error = Scratchcard::parse(test_card).unwrap_err();
+        assert_eq!(
+            error,
+            ChallengeError::parse("8b", 20, "Could not parse number")
+        );
+    }
+
+    #[test]
+    fn ch04_scratchcard_parse_bad_id() {
+        let test_card = "Card   x1: 41 48 | 83 86";
+        let error = Scratchcard::parse(test_card).unwrap_err();
+        assert_eq!(
+            error,
+            ChallengeError::parse("x1", 8, "Could not parse card ID")
+        );
+    }
+
+    #[test]
+    fn ch04_scratchcard_parse_missing_separator() {
+        let test_card = "Card 1: 41 48 83 86 17";
+        let error = Scratchcard::parse(test_card).unwrap_err();
+        assert_eq!(
+            error,
+            ChallengeError::parse(" 41 48 83 86 17", 8, "Could not find '|' separator")
+        );
+    }
 }
@@ -1,7 +1,15 @@
-use crate::utils::to_lines_vec;
+use crate::{answer::Answer, context::SolveContext, error::ChallengeError, utils::to_lines_vec};
 
-use super::Challenge;
-use std::fs::File;
+use super::{Challenge, Registration};
+use std::io::BufRead;
+
+pub const REGISTRATION: Registration = Registration {
+    year: 2023,
+    day: 1,
+    title: "Trebuchet?!",
+    version: 1,
+    create: || Box::new(Trebuchet::default()),
+};
 
 #[derive(Default)]
 pub struct Trebuchet {
@@ -9,43 +17,70 @@ pub struct Trebuchet {
 }
 
 impl Challenge for Trebuchet {
-    fn load(&mut self, file: &File) {
-        self.lines = to_lines_vec(file);
+    fn load(&mut self, reader: &mut dyn BufRead) -> Result<(), ChallengeError> {
+        self.lines = to_lines_vec(reader)?;
+        Ok(())
     }
-    fn solve_part_one(&self) -> String {
+    fn solve_part_one(&self, context: &SolveContext) -> Result<Answer, ChallengeError> {
         let mut values: Vec<u32> = Vec::new();
-        for line in self.lines.clone() {
-            let (first, last) = Trebuchet::get_first_and_last_digits(&line);
+        for (i, line) in self.lines.iter().enumerate() {
+            let (first, last) =
+                Trebuchet::get_first_and_last_digits(line).map_err(|e| e.at_line(i + 1))?;
             let num_str = format!("{}{}", first, last);
-            values.push(num_str.parse::<u32>().unwrap());
+            context
+                .explain()
+                .step(format_args!("{}: {} and {} -> {}", line, first, last, num_str));
+            values.push(num_str.parse::<u32>().map_err(|_| {
+                ChallengeError::solve(&format!("Could not read {} as a number.", num_str))
+            })?);
         }
 
         let sum = values.iter().sum::<u32>();
 
-        format!("{}", sum)
+        Ok(Answer::from(sum))
     }
-    fn solve_part_two(&self) -> String {
+    fn solve_part_two(&self, context: &SolveContext) -> Result<Answer, ChallengeError> {
         let mut values = Vec::new();
-        for line in self.lines.clone() {
-            let (first, last) = Trebuchet::get_first_and_last_numstr_or_digit(&line);
+        for (i, line) in self.lines.iter().enumerate() {
+            let (first, last) = Trebuchet::get_first_and_last_numstr_or_digit(line)
+                .map_err(|e| e.at_line(i + 1))?;
             let num_str = format!("{}{}", first, last);
-            println!("{}: {}", line, num_str);
-            values.push(num_str.parse::<u32>().unwrap());
+            context
+                .trace()
+                .trace(format_args!("{}: {}", line, num_str));
+            context
+                .explain()
+                .step(format_args!("{}: {} and {} -> {}", line, first, last, num_str));
+            values.push(num_str.parse::<u32>().map_err(|_| {
+                ChallengeError::solve(&format!("Could not read {} as a number.", num_str))
+            })?);
         }
 
         let sum = values.iter().sum::<u32>();
 
-        format!("{}", sum)
+        Ok(Answer::from(sum))
+    }
+    // Both parts read the same line, so the REPL shows what each finds.
+    fn parse_line(&self, line: &str) -> Option<Result<String, ChallengeError>> {
+        let describe = |digits: Result<(char, char), ChallengeError>| match digits {
+            Ok((first, last)) => format!("{} and {}", first, last),
+            Err(error) => error.to_string(),
+        };
+        Some(Ok(format!(
+            "Digits: {}\nDigits and words: {}",
+            describe(Trebuchet::get_first_and_last_digits(line)),
+            describe(Trebuchet::get_first_and_last_numstr_or_digit(line))
+        )))
     }
 }
 
 impl Trebuchet {
-    fn get_first_and_last_digits(line: &str) -> (char, char) {
-        let digits: Vec<char> = line.chars().filter(|c| c.is_digit(10)).collect();
-        if digits.len() == 0 {
-            panic!("No digits found in line: {}", line);
+    fn get_first_and_last_digits(line: &str) -> Result<(char, char), ChallengeError> {
+        let digits: Vec<char> = line.chars().filter(|c| c.is_ascii_digit()).collect();
+        if digits.is_empty() {
+            return Err(ChallengeError::parse(line, 1, "No digits found in line"));
         }
-        (digits[0], digits[digits.len() - 1])
+        Ok((digits[0], digits[digits.len() - 1]))
     }
 
     fn find_all_number_substrings(input: &str) -> Vec<char> {
@@ -65,31 +100,40 @@ impl Trebuchet {
         found_substrings
             .iter()
             .map(|&(_, substrings_index)| {
-                char::from_digit(((substrings_index % 9) + 1) as u32, 10).unwrap()
+                ['1', '2', '3', '4', '5', '6', '7', '8', '9'][substrings_index % 9]
             })
             .collect()
     }
 
-    fn get_first_and_last_numstr_or_digit(line: &str) -> (char, char) {
+    fn get_first_and_last_numstr_or_digit(line: &str) -> Result<(char, char), ChallengeError> {
         let substrings = Trebuchet::find_all_number_substrings(line);
-        if substrings.len() == 0 {
-            panic!("No digits found in line: {}", line);
+        if substrings.is_empty() {
+            return Err(ChallengeError::parse(line, 1, "No digits found in line"));
         }
         let first = substrings[0];
         let last = substrings[substrings.len() - 1];
 
-        (first, last)
+        Ok((first, last))
     }
 }
 
 #[cfg(test)]
 mod test {
+    use std::rc::Rc;
+
     use super::*;
+    use crate::trace::TraceLevel;
+
+    fn load_input(lines: &[&str]) -> Trebuchet {
+        let mut trebuchet = Trebuchet::default();
+        trebuchet.load_str(&lines.join("\n")).unwrap();
+        trebuchet
+    }
 
     #[test]
     fn ch01_get_first_and_last_digit() {
         let line = "aerv5ndsfjvn8anrve9erferf2fwef";
-        let (first, last) = Trebuchet::get_first_and_last_digits(line);
+        let (first, last) = Trebuchet::get_first_and_last_digits(line).unwrap();
         assert_eq!(first, '5');
         assert_eq!(last, '2');
     }
@@ -97,27 +141,56 @@ mod test {
     #[test]
     fn ch01_get_first_and_last_digit_with_one_digit() {
         let line = "esirvioernivoen5aiorjnvioerjogeij";
-        let (first, last) = Trebuchet::get_first_and_last_digits(line);
+        let (first, last) = Trebuchet::get_first_and_last_digits(line).unwrap();
         assert_eq!(first, '5');
         assert_eq!(last, '5');
     }
 
     #[test]
     fn ch01_test_part_one() {
-        let mut trebuchet = Trebuchet::default();
-        trebuchet.lines = vec![
-            String::from("1abc2"),
-            String::from("pqr3stu8vwx"),
-            String::from("a1b2c3d4e5f"),
-            String::from("treb7uchet"),
-        ];
-        assert_eq!(trebuchet.solve_part_one(), "142");
+        let trebuchet = load_input(&["1abc2", "pqr3stu8vwx", "a1b2c3d4e5f", "treb7uchet"]);
+        assert_eq!(trebuchet.solve_part_one(&SolveContext::default()).unwrap(), "142");
+    }
+
+    #[test]
+    fn ch01_part_two_traces_each_line() {
+        let trebuchet = load_input(&["two1nine", "eightwothree"]);
+        let (trace, captured) = crate::trace::test::captured(TraceLevel::Trace);
+        let context = SolveContext::default().with_trace(Rc::new(trace));
+
+        assert_eq!(trebuchet.solve_part_two(&context).unwrap(), "112");
+        assert_eq!(
+            captured.contents(),
+            "[trace] two1nine: 29\n[trace] eightwothree: 83\n"
+        );
+    }
+
+    #[test]
+    fn ch01_part_two_explains_each_line() {
+        let trebuchet = load_input(&["two1nine", "4nineeightseven2"]);
+        let (explain, captured) = crate::explain::test::captured();
+        let context = SolveContext::default().with_explain(Rc::new(explain));
+
+        trebuchet.solve_part_two(&context).unwrap();
+        assert_eq!(
+            captured.contents(),
+            "  two1nine: 2 and 9 -> 29\n  4nineeightseven2: 4 and 2 -> 42\n"
+        );
+    }
+
+    #[test]
+    fn ch01_get_first_and_last_digit_with_no_digits() {
+        let error = Trebuchet::get_first_and_last_digits("abc").unwrap_err();
+        assert_eq!(
+            error,
+            ChallengeError::parse("abc", 1, "No digits found in line")
+        );
     }
 
     #[test]
     fn ch01_get_first_and_last_numstr_or_digit() {
         let line = "rehfujoneruvjeu4rjvijefiveuerfijer8fnjrnbv";
-        let (first, last) = Trebuchet::get_first_and_last_numstr_or_digit(line);
+        let (first, last) = Trebuchet::get_first_and_last_numstr_or_digit(line).unwrap();
         assert_eq!(first, '1');
         assert_eq!(last, '8');
     }
@@ -125,7 +198,7 @@ mod test {
     #[test]
     fn ch01_get_first_and_last_numstr_or_digit_with_one_digit() {
         let line = "eruvjneiruvnfoureruvn";
-        let (first, last) = Trebuchet::get_first_and_last_numstr_or_digit(line);
+        let (first, last) = Trebuchet::get_first_and_last_numstr_or_digit(line).unwrap();
         assert_eq!(first, '4');
         assert_eq!(last, '4');
     }
@@ -176,50 +249,57 @@ mod test {
 
     #[test]
     fn ch01_test_part_two() {
-        let mut trebuchet = Trebuchet::default();
-        trebuchet.lines = vec![
-            String::from("two1nine"),
-            String::from("eightwothree"),
-            String::from("abcone2threexyz"),
-            String::from("xtwone3four"),
-            String::from("4nineeightseven2"),
-            String::from("zoneight234"),
-            String::from("7pqrstsixteen"),
-        ];
-        assert_eq!(trebuchet.solve_part_two(), "281");
+        let trebuchet = load_input(&[
+            "two1nine",
+            "eightwothree",
+            "abcone2threexyz",
+            "xtwone3four",
+            "4nineeightseven2",
+            "zoneight234",
+            "7pqrstsixteen",
+        ]);
+        assert_eq!(trebuchet.solve_part_two(&SolveContext::default()).unwrap(), "281");
     }
 
     #[test]
     fn ch01_test_part_two_all_possible() {
-        let mut trebuchet = Trebuchet::default();
-        trebuchet.lines = vec![
-            String::from("one384h3two1"),
-            String::from("2hhhhhhhhtwo"),
-            String::from("threebvghbvhgb3"),
-            String::from("4nevjrkvnerkjtfour"),
-            String::from("584952849582five"),
-            String::from("sixsevenonetwofour23423nine6"),
-            String::from("7seven"),
-            String::from("eight"),
-            String::from("nine9nine"),
-        ];
-        assert_eq!(trebuchet.solve_part_two(), "495");
+        let trebuchet = load_input(&[
+            "one384h3two1",
+            "2hhhhhhhhtwo",
+            "threebvghbvhgb3",
+            "4nevjrkvnerkjtfour",
+            "584952849582five",
+            "sixsevenonetwofour23423nine6",
+            "7seven",
+            "eight",
+            "nine9nine",
+        ]);
+        assert_eq!(trebuchet.solve_part_two(&SolveContext::default()).unwrap(), "495");
     }
 
     #[test]
     fn ch01_test_part_two_2() {
-        let mut trebuchet = Trebuchet::default();
-        trebuchet.lines = vec![
-            String::from("f3"),                                                // 33
-            String::from("6zzrfxdxseventhree"),                                // 63
-            String::from("265one"),                                            // 21
-            String::from("seven3lbcvjxqhhdpzkttqsixjzzjjbclfq1fiveeightwojx"), // 72
-            String::from("seightwoone8qxcfgszninesvfcnxc68"),                  // 88
-            String::from("strqnb5eightbpnkcjdz6"),                             // 56
-            String::from("fiveninebtpbpjqbgx2bmjrgmprnd"),                     // 52
-            String::from("sixgtxr2fourrdkjg"),                                 // 64
-            String::from("fivebxsevensixone872dlx"),                           // 52
-        ];
-        assert_eq!(trebuchet.solve_part_two(), "501");
+        let trebuchet = load_input(&[
+            "f3",                                                // 33
+            "6zzrfxdxseventhree",                                // 63
+            "265one",                                            // 21
+            "seven3lbcvjxqhhdpzkttqsixjzzjjbclfq1fiveeightwojx", // 72
+            "seightwoone8qxcfgszninesvfcnxc68",                  // 88
+            "strqnb5eightbpnkcjdz6",                             // 56
+            "fiveninebtpbpjqbgx2bmjrgmprnd",                     // 52
+            "sixgtxr2fourrdkjg",                                 // 64
+            "fivebxsevensixone872dlx",                           // 52
+        ]);
+        assert_eq!(trebuchet.solve_part_two(&SolveContext::default()).unwrap(), "501");
+    }
+
+    #[test]
+    fn ch01_test_part_one_reports_line() {
+        let trebuchet = load_input(&["1abc2", "pqrstu"]);
+        let error = trebuchet.solve_part_one(&SolveContext::default()).unwrap_err();
+        assert_eq!(
+            error,
+            ChallengeError::parse("pqrstu", 1, "No digits found in line").at_line(2)
+        );
     }
 }
@@ -1,78 +1,127 @@
-use crate::utils::to_lines_vec;
+use crate::{answer::Answer, context::SolveContext, error::ChallengeError, utils::{split_offsets, to_lines_vec}};
 
-use self::race::RaceInfo;
+pub use self::race::{Race, RaceInfo};
 
-use super::Challenge;
-use std::fs::File;
+use super::{Challenge, Registration};
+use std::io::BufRead;
 
 mod race;
 
+pub const REGISTRATION: Registration = Registration {
+    year: 2023,
+    day: 6,
+    title: "Wait For It",
+    version: 1,
+    create: || Box::new(WaitForIt::default()),
+};
+
 #[derive(Default)]
 pub struct WaitForIt {
     races: Vec<RaceInfo>,
-    lines: Vec<String>,
+    combined_race: RaceInfo,
 }
 
 impl WaitForIt {
-    pub fn load(&mut self, lines: Vec<String>) {
-        self.lines = lines.clone();
-        let mut lines_iter = lines.into_iter();
-        let time_line = lines_iter.next().expect("Time line missing.");
-        let distance_line = lines_iter.next().expect("Distance line missing.");
-
-        self.races = time_line
-            .split(' ')
-            .filter(|x| !x.is_empty())
-            .zip(distance_line.split(' ').filter(|x| !x.is_empty()))
-            .skip(1)
-            .map(|(time_str, dist_str)| {
-                let time = time_str.parse().expect("Unable to parse time string.");
-                let distance = dist_str.parse().expect("Unable to parse distanc string.");
-
-                RaceInfo::new(time, distance)
-            })
+    fn load_lines(&mut self, lines: Vec<String>) -> Result<(), ChallengeError> {
+        let mut lines_iter = lines.iter();
+        let time_line = lines_iter
+            .next()
+            .ok_or_else(|| ChallengeError::parse("", 1, "Time line missing.").at_line(1))?;
+        let distance_line = lines_iter
+            .next()
+            .ok_or_else(|| ChallengeError::parse("", 1, "Distance line missing.").at_line(2))?;
+
+        let times = parse_values(time_line, "Time").map_err(|e| e.at_line(1))?;
+        let distances = parse_values(distance_line, "Distance").map_err(|e| e.at_line(2))?;
+        if times.len() != distances.len() {
+            return Err(ChallengeError::parse(
+                distance_line,
+                1,
+                "Expected a distance for every time.",
+            )
+            .at_line(2));
+        }
+
+        self.races = times
+            .iter()
+            .zip(&distances)
+            .map(|(time, distance)| RaceInfo::new(*time, *distance))
             .collect();
+        self.combined_race = RaceInfo::new(
+            combine_values(time_line).map_err(|e| e.at_line(1))?,
+            combine_values(distance_line).map_err(|e| e.at_line(2))?,
+        );
+
+        Ok(())
+    }
+}
+
+fn parse_values(line: &str, label: &str) -> Result<Vec<u64>, ChallengeError> {
+    let (prefix, values) = line.split_once(':').ok_or_else(|| {
+        ChallengeError::parse(line, 1, &format!("Expected a '{}:' label.", label))
+    })?;
+    if prefix != label {
+        return Err(ChallengeError::parse(
+            prefix,
+            1,
+            &format!("Expected a '{}:' label.", label),
+        ));
     }
+
+    let values_offset = prefix.len() + 1;
+    split_offsets(values, ' ')
+        .filter(|(_, x)| !x.is_empty())
+        .map(|(offset, value)| {
+            value.parse::<u64>().map_err(|_| {
+                ChallengeError::parse(value, values_offset + offset + 1, "Unable to parse number.")
+            })
+        })
+        .collect()
+}
+
+fn combine_values(line: &str) -> Result<u64, ChallengeError> {
+    let offset = line.find(':').map_or(0, |i| i + 1);
+    let values = &line[offset..];
+    values
+        .split(' ')
+        .filter(|x| !x.is_empty())
+        .fold(String::new(), |a, c| format!("{}{}", a, c))
+        .parse::<u64>()
+        .map_err(|_| ChallengeError::parse(values, offset + 1, "Combined number is too large."))
 }
 
 impl Challenge for WaitForIt {
-    fn load(&mut self, file: &File) {
-        self.load(to_lines_vec(file));
+    fn load(&mut self, reader: &mut dyn BufRead) -> Result<(), ChallengeError> {
+        self.load_lines(to_lines_vec(reader)?)
     }
-    fn solve_part_one(&self) -> String {
+    fn solve_part_one(&self, _context: &SolveContext) -> Result<Answer, ChallengeError> {
         let ways_to_win: usize = self
             .races
             .iter()
             .map(|race| race.all_winning_races().len())
             .product();
 
-        format!("{}", ways_to_win)
+        Ok(Answer::from(ways_to_win))
     }
-    fn solve_part_two(&self) -> String {
-        let mut lines_iter = self.lines.iter();
-        let time = lines_iter
-            .next()
-            .expect("Unable to parse time line.")
-            .split(' ')
-            .skip(1)
-            .filter(|x| !x.is_empty())
-            .fold(String::new(), |a, c| format!("{}{}", a, c))
-            .parse::<u64>()
-            .expect("Unable to parse time string.");
-        let distance = lines_iter
-            .next()
-            .expect("Unable to parse distance line.")
-            .split(' ')
-            .skip(1)
-            .filter(|x| !x.is_empty())
-            .fold(String::new(), |a, c| format!("{}{}", a, c))
-            .parse::<u64>()
-            .expect("Unable to parse distance string.");
-
-        let race = RaceInfo::new(time, distance);
-        let ways_to_win = race.all_winning_races().len();
-
-        format!("{}", ways_to_win)
+    fn solve_part_two(&self, _context: &SolveContext) -> Result<Answer, ChallengeError> {
+        let ways_to_win = self.combined_race.all_winning_races().len();
+
+        Ok(Answer::from(ways_to_win))
+    }
+    fn parse_line(&self, line: &str) -> Option<Result<String, ChallengeError>> {
+        let label = if line.starts_with("Distance") {
+            "Distance"
+        } else {
+            "Time"
+        };
+        Some(parse_values(line, label).and_then(|values| {
+            Ok(format!(
+                "{}: {:?}, combined {}",
+                label,
+                values,
+                combine_values(line)?
+            ))
+        }))
     }
 }
 
@@ -82,27 +131,45 @@ mod test {
 
     #[test]
     fn ch06_waitforit_part_one() {
-        let lines: Vec<String> = vec![
-            "Time:      7  15   30".into(),
-            "Distance:  9  40  200".into(),
-        ];
+        let input = "Time:      7  15   30\nDistance:  9  40  200";
 
         let mut wait_for_it = WaitForIt::default();
-        wait_for_it.load(lines);
+        wait_for_it.load_str(input).unwrap();
 
-        assert_eq!(wait_for_it.solve_part_one(), "288");
+        assert_eq!(wait_for_it.solve_part_one(&SolveContext::default()).unwrap(), "288");
     }
 
     #[test]
     fn ch06_waitforit_part_two() {
-        let lines: Vec<String> = vec![
-            "Time:      7  15   30".into(),
-            "Distance:  9  40  200".into(),
-        ];
+        let input = "Time:      7  15   30\nDistance:  9  40  200";
 
         let mut wait_for_it = WaitForIt::default();
-        wait_for_it.load(lines);
+        wait_for_it.load_str(input).unwrap();
+
+        assert_eq!(wait_for_it.solve_part_two(&SolveContext::default()).unwrap(), "71503");
+    }
+
+    #[test]
+    fn ch06_waitforit_load_bad_number() {
+        let input = "Time:      7  15   30\nDistance:  9  4O  200";
 
-        assert_eq!(wait_for_it.solve_part_two(), "71503");
+        let mut wait_for_it = WaitForIt::default();
+        let error = wait_for_it.load_str(input).unwrap_err();
+        assert_eq!(
+            error,
+            ChallengeError::parse("4O", 15, "Unable to parse number.").at_line(2)
+        );
+    }
+
+    #[test]
+    fn ch06_waitforit_load_missing_distance() {
+        let input = "Time:      7  15   30";
+
+        let mut wait_for_it = WaitForIt::default();
+        let error = wait_for_it.load_str(input).unwrap_err();
+        assert_eq!(
+            error,
+            ChallengeError::parse("", 1, "Distance line missing.").at_line(2)
+        );
     }
 }
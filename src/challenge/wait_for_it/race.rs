@@ -1,22 +1,18 @@
 pub struct Race {
-    pub hold_time: u64,
     pub travel_distance: u64,
 }
 
 impl Race {
-    pub fn new(total_time: u64, hold_time: u64) -> Self {
-        if hold_time >= total_time {
-            panic!("Held the button down for too long, race failed.");
-        }
-        let travel_distance = hold_time * (total_time - hold_time);
+    // None when the button is held for the whole race.
+    pub fn new(total_time: u64, hold_time: u64) -> Option<Self> {
+        let travel_time = total_time.checked_sub(hold_time).filter(|time| *time > 0)?;
+        let travel_distance = hold_time.saturating_mul(travel_time);
 
-        Race {
-            hold_time,
-            travel_distance,
-        }
+        Some(Race { travel_distance })
     }
 }
 
+#[derive(Default)]
 pub struct RaceInfo {
     time: u64,
     record_distance: u64,
@@ -37,7 +33,9 @@ impl RaceInfo {
     pub fn all_winning_races(&self) -> Vec<Race> {
         let mut result = Vec::new();
         for i in 1..self.time {
-            let race = Race::new(self.time, i);
+            let Some(race) = Race::new(self.time, i) else {
+                continue;
+            };
             if self.beats_record(&race) {
                 result.push(race);
             }
@@ -52,8 +50,9 @@ mod test {
 
     #[test]
     fn ch06_raceinfo_beats_record() {
-        let race = Race::new(7, 3);
+        let race = Race::new(7, 3).unwrap();
         assert_eq!(race.travel_distance, 12);
+        assert!(Race::new(7, 7).is_none());
 
         let race_info = RaceInfo::new(7, 9);
         assert!(race_info.beats_record(&race));
@@ -0,0 +1,262 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{config::Settings, error::ChallengeError};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+pub const DEFAULT_STATE_DIR: &str = "./.aoc-state";
+pub const DEFAULT_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+const USER_AGENT: &str = "github.com/WVAviator/advent-of-code-2023";
+
+// Spaces out requests to the puzzle server. The time of the last request is
+// kept in a file so that separate invocations share the limit.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    path: PathBuf,
+    interval: Duration,
+}
+
+impl RateLimiter {
+    pub fn new(state_dir: &Path, interval: Duration) -> Self {
+        RateLimiter {
+            path: state_dir.join("last-request"),
+            interval,
+        }
+    }
+
+    // Sleeps until the interval since the last request has passed, then
+    // records this request. Returns how long it slept.
+    pub fn wait(&self) -> Result<Duration, ChallengeError> {
+        let now = || {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+        };
+        let last = fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|content| content.trim().parse::<u64>().ok())
+            .map(Duration::from_millis);
+        let waited = last
+            .map(|last| (last + self.interval).saturating_sub(now()))
+            .unwrap_or_default()
+            .min(self.interval);
+        thread::sleep(waited);
+
+        let error = |e: std::io::Error| {
+            ChallengeError::io(&format!("Could not write {}: {}", self.path.display(), e))
+        };
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(error)?;
+        }
+        fs::write(&self.path, now().as_millis().to_string()).map_err(error)?;
+
+        Ok(waited)
+    }
+}
+
+pub fn session(settings: &Settings) -> Result<String, ChallengeError> {
+    settings.get("session").map(String::from).ok_or_else(|| {
+        ChallengeError::io(&format!(
+            "No session token. Set {} or add session to aoc.toml",
+            SESSION_ENV
+        ))
+    })
+}
+
+// Talks to the puzzle server, or to any stand-in that serves the same paths.
+pub struct Client {
+    base_url: String,
+    session: String,
+    limiter: RateLimiter,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str, limiter: RateLimiter) -> Self {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            limiter,
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .user_agent(USER_AGENT)
+                .build(),
+        }
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    pub fn get(&self, path: &str) -> Result<String, ChallengeError> {
+        self.limiter.wait()?;
+        let response = self
+            .agent
+            .get(&self.url(path))
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+
+        self.read(path, response)
+    }
+
+    pub fn post_form(&self, path: &str, form: &[(&str, &str)]) -> Result<String, ChallengeError> {
+        self.limiter.wait()?;
+        let response = self
+            .agent
+            .post(&self.url(path))
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(form);
+
+        self.read(path, response)
+    }
+
+    fn read(
+        &self,
+        path: &str,
+        response: Result<ureq::Response, ureq::Error>,
+    ) -> Result<String, ChallengeError> {
+        let url = self.url(path);
+        match response {
+            Ok(response) => response.into_string().map_err(|e| {
+                ChallengeError::io(&format!("Could not read the response from {}: {}", url, e))
+            }),
+            Err(ureq::Error::Status(status, response)) => {
+                let body = response.into_string().unwrap_or_default();
+                Err(ChallengeError::io(&format!(
+                    "{} responded with {}: {}",
+                    url,
+                    status,
+                    body.lines().next().unwrap_or_default()
+                )))
+            }
+            Err(e) => Err(ChallengeError::io(&format!(
+                "Could not reach {}: {}",
+                url, e
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::JoinHandle,
+    };
+
+    use super::*;
+    use crate::{config::Config, utils::temp_dir::TempDir};
+
+    // A stand-in puzzle server that answers each connection with the next
+    // canned response, then hands back the raw requests it received.
+    pub(crate) fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        length = value.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+                let mut body_bytes = vec![0; length];
+                reader.read_exact(&mut body_bytes).unwrap();
+                request.push_str(&String::from_utf8(body_bytes).unwrap());
+                requests.push(request);
+
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 {} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+            requests
+        });
+
+        (base_url, handle)
+    }
+
+    pub(crate) fn client(base_url: &str, state_dir: &Path) -> Client {
+        Client::new(
+            base_url,
+            "secret",
+            RateLimiter::new(state_dir, Duration::ZERO),
+        )
+    }
+
+    #[test]
+    fn client_sends_session_cookie() {
+        let (base_url, server) = serve(vec![(200, "1abc2\n")]);
+        let dir = TempDir::new("client-cookie");
+
+        let body = client(&format!("{}/", base_url), &dir)
+            .get("/2023/day/1/input")
+            .unwrap();
+        let requests = server.join().unwrap();
+
+        assert_eq!(body, "1abc2\n");
+        assert!(requests[0].starts_with("GET /2023/day/1/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("session=secret"));
+    }
+
+    #[test]
+    fn client_reports_error_status() {
+        let (base_url, server) = serve(vec![(404, "Not found\nmore")]);
+        let dir = TempDir::new("client-status");
+
+        let error = client(&base_url, &dir).get("/2023/day/26/input").err();
+        server.join().unwrap();
+
+        assert_eq!(
+            error,
+            Some(ChallengeError::io(&format!(
+                "{}/2023/day/26/input responded with 404: Not found",
+                base_url
+            )))
+        );
+    }
+
+    #[test]
+    fn client_session_is_required() {
+        let settings =
+            |config: &str| Settings::resolve(|_| None, |_| None, &Config::parse(config).unwrap());
+
+        assert_eq!(
+            session(&settings("session = \"abc\"")),
+            Ok(String::from("abc"))
+        );
+        assert!(session(&settings("")).is_err());
+    }
+
+    #[test]
+    fn client_rate_limiter_spaces_requests() {
+        let dir = TempDir::new("client-limiter");
+        let limiter = RateLimiter::new(&dir, Duration::from_millis(50));
+
+        assert_eq!(limiter.wait().unwrap(), Duration::ZERO);
+        assert!(limiter.wait().unwrap() > Duration::ZERO);
+    }
+}
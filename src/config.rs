@@ -0,0 +1,324 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    fs, io,
+    path::Path,
+};
+
+use crate::{
+    client::{BASE_URL_ENV, DEFAULT_BASE_URL, SESSION_ENV},
+    error::ChallengeError,
+    runner::DEFAULT_INPUT_PATTERN,
+    utils::parse_toml_table,
+};
+
+pub const CONFIG_PATH: &str = "./aoc.toml";
+
+// A configurable value. A command line flag wins over the environment
+// variable, which wins over `aoc.toml`, which wins over the default.
+#[derive(Debug, PartialEq)]
+pub struct Key {
+    pub name: &'static str,
+    pub flag: Option<&'static str>,
+    pub env: &'static str,
+    pub default: Option<&'static str>,
+    // Hidden by `config show`.
+    pub secret: bool,
+}
+
+const fn key(
+    name: &'static str,
+    flag: Option<&'static str>,
+    env: &'static str,
+    default: Option<&'static str>,
+) -> Key {
+    Key {
+        name,
+        flag,
+        env,
+        default,
+        secret: false,
+    }
+}
+
+pub const KEYS: [Key; 8] = [
+    key("inputs", None, "AOC_INPUTS", Some(DEFAULT_INPUT_PATTERN)),
+    key("year", Some("year"), "AOC_YEAR", Some("2023")),
+    key("format", Some("format"), "AOC_FORMAT", Some("text")),
+    key("profile", Some("profile"), "AOC_PROFILE", None),
+    key("timeout", Some("timeout"), "AOC_TIMEOUT", None),
+    key("trace", Some("trace"), "AOC_TRACE", Some("off")),
+    Key {
+        secret: true,
+        ..key("session", None, SESSION_ENV, None)
+    },
+    key(
+        "base_url",
+        Some("base-url"),
+        BASE_URL_ENV,
+        Some(DEFAULT_BASE_URL),
+    ),
+];
+
+// The contents of `aoc.toml`, e.g. `year = 2022` or `format = "json"`.
+#[derive(Debug, Default, PartialEq)]
+pub struct Config {
+    values: BTreeMap<String, String>,
+}
+
+impl Config {
+    // A missing file is the same as an empty one.
+    pub fn load(path: &Path) -> Result<Self, ChallengeError> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Config::default()),
+            Err(e) => {
+                return Err(ChallengeError::io(&format!(
+                    "Could not read {}: {}",
+                    path.display(),
+                    e
+                )))
+            }
+        };
+
+        Config::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Result<Self, ChallengeError> {
+        let mut values = BTreeMap::new();
+        for (name, value) in parse_toml_table(content)? {
+            if !KEYS.iter().any(|key| key.name == name) {
+                let names: Vec<&str> = KEYS.iter().map(|key| key.name).collect();
+                return Err(ChallengeError::parse(
+                    &name,
+                    1,
+                    &format!("Unknown key. Expected one of {}", names.join(", ")),
+                ));
+            }
+            let value = match value {
+                toml::Value::String(value) => value,
+                toml::Value::Integer(value) => value.to_string(),
+                toml::Value::Float(value) => value.to_string(),
+                _ => {
+                    return Err(ChallengeError::parse(
+                        &name,
+                        1,
+                        "Expected a string or a number",
+                    ))
+                }
+            };
+            values.insert(name, value);
+        }
+
+        Ok(Config { values })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Source {
+    Flag(&'static str),
+    Env(&'static str),
+    Config,
+    Default,
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Flag(flag) => write!(f, "--{}", flag),
+            Source::Env(name) => write!(f, "{}", name),
+            Source::Config => write!(f, "aoc.toml"),
+            Source::Default => write!(f, "default"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Setting {
+    pub key: &'static Key,
+    pub value: Option<String>,
+    pub source: Source,
+}
+
+// The effective value of every key, and where it came from.
+#[derive(Debug, PartialEq)]
+pub struct Settings {
+    settings: Vec<Setting>,
+}
+
+impl Settings {
+    // `flag` and `env` look up a flag or variable by name. Empty environment
+    // variables count as unset.
+    pub fn resolve(
+        flag: impl Fn(&str) -> Option<String>,
+        env: impl Fn(&str) -> Option<String>,
+        config: &Config,
+    ) -> Self {
+        let settings = KEYS
+            .iter()
+            .map(|key| {
+                let (value, source) = if let Some(value) = key.flag.and_then(&flag) {
+                    (Some(value), Source::Flag(key.flag.unwrap_or_default()))
+                } else if let Some(value) = env(key.env).filter(|value| !value.is_empty()) {
+                    (Some(value), Source::Env(key.env))
+                } else if let Some(value) = config.get(key.name) {
+                    (Some(value.to_string()), Source::Config)
+                } else {
+                    (key.default.map(String::from), Source::Default)
+                };
+                Setting { key, value, source }
+            })
+            .collect();
+
+        Settings { settings }
+    }
+
+    pub fn all(&self) -> &[Setting] {
+        &self.settings
+    }
+
+    pub fn setting(&self, name: &str) -> &Setting {
+        self.settings
+            .iter()
+            .find(|setting| setting.key.name == name)
+            .expect("Settings cover every key.")
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.setting(name).value.as_deref()
+    }
+
+    // Errors name the source, so a bad value in aoc.toml is easy to find.
+    pub fn parse<T>(
+        &self,
+        name: &str,
+        parse: impl FnOnce(&str) -> Option<T>,
+    ) -> Result<Option<T>, ChallengeError> {
+        let setting = self.setting(name);
+        match setting.value.as_deref() {
+            Some(value) => parse(value).map(Some).ok_or_else(|| {
+                ChallengeError::io(&format!(
+                    "Invalid {} '{}' from {}",
+                    name, value, setting.source
+                ))
+            }),
+            None => Ok(None),
+        }
+    }
+
+    pub fn show(&self) -> String {
+        let width = KEYS.iter().map(|key| key.name.len()).max().unwrap_or(0);
+        self.settings
+            .iter()
+            .map(|setting| {
+                let value = match &setting.value {
+                    Some(_) if setting.key.secret => String::from("(hidden)"),
+                    Some(value) => value.clone(),
+                    None => String::from("(unset)"),
+                };
+                format!(
+                    "{:<width$} = {} ({})",
+                    setting.key.name,
+                    value,
+                    setting.source,
+                    width = width
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::challenge::DEFAULT_YEAR;
+
+    fn lookup(pairs: &'static [(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        |name| {
+            pairs
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        }
+    }
+
+    #[test]
+    fn config_parse_known_keys() {
+        let config = Config::parse("year = 2022\nformat = \"json\"\ntimeout = 2.5").unwrap();
+
+        assert_eq!(config.get("year"), Some("2022"));
+        assert_eq!(config.get("format"), Some("json"));
+        assert_eq!(config.get("timeout"), Some("2.5"));
+        assert!(Config::parse("yaer = 2022").is_err());
+        assert!(Config::parse("profile = [\"alice\"]").is_err());
+        assert_eq!(
+            Config::load(Path::new("missing-aoc.toml")).unwrap(),
+            Config::default()
+        );
+    }
+
+    #[test]
+    fn config_precedence() {
+        let config = Config::parse("year = 2021\nformat = \"csv\"\nprofile = \"bob\"").unwrap();
+        let settings = Settings::resolve(
+            lookup(&[("year", "2023")]),
+            lookup(&[
+                ("AOC_YEAR", "2022"),
+                ("AOC_FORMAT", "json"),
+                ("AOC_PROFILE", ""),
+            ]),
+            &config,
+        );
+
+        assert_eq!(settings.get("year"), Some("2023"));
+        assert_eq!(settings.setting("year").source, Source::Flag("year"));
+        assert_eq!(settings.get("format"), Some("json"));
+        assert_eq!(settings.setting("format").source, Source::Env("AOC_FORMAT"));
+        assert_eq!(settings.get("profile"), Some("bob"));
+        assert_eq!(settings.setting("profile").source, Source::Config);
+        assert_eq!(settings.get("inputs"), Some(DEFAULT_INPUT_PATTERN));
+        assert_eq!(settings.setting("inputs").source, Source::Default);
+        assert_eq!(settings.get("timeout"), None);
+    }
+
+    #[test]
+    fn config_default_year_matches_registry() {
+        let settings = Settings::resolve(|_| None, |_| None, &Config::default());
+
+        assert_eq!(
+            settings.parse("year", |year| year.parse::<u16>().ok()),
+            Ok(Some(DEFAULT_YEAR))
+        );
+    }
+
+    #[test]
+    fn config_parse_names_source() {
+        let config = Config::parse("year = \"next\"").unwrap();
+        let settings = Settings::resolve(|_| None, |_| None, &config);
+
+        assert_eq!(
+            settings.parse("year", |year| year.parse::<u16>().ok()),
+            Err(ChallengeError::io("Invalid year 'next' from aoc.toml"))
+        );
+    }
+
+    #[test]
+    fn config_show_hides_session() {
+        let settings = Settings::resolve(
+            |_| None,
+            lookup(&[("AOC_SESSION", "53616c7465")]),
+            &Config::default(),
+        );
+        let shown = settings.show();
+
+        assert!(shown.contains("session  = (hidden) (AOC_SESSION)"));
+        assert!(shown.contains("profile  = (unset) (default)"));
+        assert!(shown.starts_with("inputs   = ./inputs/{year}/{day}.aoc (default)"));
+        assert!(!shown.contains("53616c7465"));
+    }
+}
@@ -0,0 +1,102 @@
+use std::{
+    cell::Cell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use crate::{error::ChallengeError, explain::Explain, trace::Trace};
+
+// Passed to every solve so that long-running solvers can report progress and
+// stop cooperatively once their time budget is spent. Diagnostic output goes
+// through `trace()` rather than stdout, and answer derivations through
+// `explain()`.
+#[derive(Debug, Default)]
+pub struct SolveContext {
+    timeout: Option<Duration>,
+    started: Option<Instant>,
+    progress: Cell<Option<u64>>,
+    trace: Rc<Trace>,
+    explain: Rc<Explain>,
+}
+
+impl SolveContext {
+    pub fn with_timeout(timeout: Duration) -> Self {
+        SolveContext {
+            timeout: Some(timeout),
+            started: Some(Instant::now()),
+            progress: Cell::new(None),
+            trace: Rc::default(),
+            explain: Rc::default(),
+        }
+    }
+
+    pub fn with_trace(self, trace: Rc<Trace>) -> Self {
+        SolveContext { trace, ..self }
+    }
+
+    pub fn trace(&self) -> &Trace {
+        &self.trace
+    }
+
+    pub fn with_explain(self, explain: Rc<Explain>) -> Self {
+        SolveContext { explain, ..self }
+    }
+
+    pub fn explain(&self) -> &Explain {
+        &self.explain
+    }
+
+    pub fn progress(&self) -> Option<u64> {
+        self.progress.get()
+    }
+
+    pub fn report(&self, progress: u64) {
+        self.progress.set(Some(progress));
+    }
+
+    pub fn check(&self) -> Result<(), ChallengeError> {
+        match (self.timeout, self.started) {
+            (Some(timeout), Some(started)) if started.elapsed() >= timeout => {
+                Err(ChallengeError::TimedOut {
+                    day: None,
+                    timeout,
+                    progress: self.progress(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // Records progress and then checks the time budget.
+    pub fn tick(&self, progress: u64) -> Result<(), ChallengeError> {
+        self.report(progress);
+        self.check()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn context_without_timeout_never_expires() {
+        let context = SolveContext::default();
+
+        assert_eq!(context.tick(5), Ok(()));
+        assert_eq!(context.progress(), Some(5));
+    }
+
+    #[test]
+    fn context_reports_last_progress_on_timeout() {
+        let context = SolveContext::with_timeout(Duration::ZERO);
+
+        assert_eq!(
+            context.tick(42),
+            Err(ChallengeError::TimedOut {
+                day: None,
+                timeout: Duration::ZERO,
+                progress: Some(42),
+            })
+        );
+    }
+}
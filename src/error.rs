@@ -0,0 +1,225 @@
+use std::{fmt::Display, time::Duration};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChallengeError {
+    Parse {
+        day: Option<u8>,
+        line: Option<usize>,
+        column: usize,
+        text: String,
+        message: String,
+    },
+    Solve {
+        day: Option<u8>,
+        message: String,
+    },
+    Io {
+        day: Option<u8>,
+        message: String,
+    },
+    TimedOut {
+        day: Option<u8>,
+        timeout: Duration,
+        progress: Option<u64>,
+    },
+    Unregistered {
+        year: u16,
+        day: u8,
+    },
+}
+
+impl ChallengeError {
+    pub fn parse(text: &str, column: usize, message: &str) -> Self {
+        ChallengeError::Parse {
+            day: None,
+            line: None,
+            column,
+            text: text.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    pub fn solve(message: &str) -> Self {
+        ChallengeError::Solve {
+            day: None,
+            message: message.to_string(),
+        }
+    }
+
+    pub fn unknown_solver(name: &str, available: &[&str]) -> Self {
+        ChallengeError::solve(&format!(
+            "Unknown solver '{}'. Available solvers: {}",
+            name,
+            available.join(", ")
+        ))
+    }
+
+    pub fn unknown_command(name: &str, available: &[&str]) -> Self {
+        ChallengeError::solve(&format!(
+            "Unknown command '{}'. Available commands: {}",
+            name,
+            available.join(", ")
+        ))
+    }
+
+    pub fn io(message: &str) -> Self {
+        ChallengeError::Io {
+            day: None,
+            message: message.to_string(),
+        }
+    }
+
+    pub fn at_line(mut self, line_number: usize) -> Self {
+        if let ChallengeError::Parse { line, .. } = &mut self {
+            *line = Some(line_number);
+        }
+        self
+    }
+
+    pub fn offset_lines(mut self, lines: usize) -> Self {
+        if let ChallengeError::Parse {
+            line: Some(line), ..
+        } = &mut self
+        {
+            *line += lines;
+        }
+        self
+    }
+
+    pub fn offset_columns(mut self, columns: usize) -> Self {
+        if let ChallengeError::Parse { column, .. } = &mut self {
+            *column += columns;
+        }
+        self
+    }
+
+    pub fn with_day(mut self, challenge_id: u8) -> Self {
+        match &mut self {
+            ChallengeError::Parse { day, .. }
+            | ChallengeError::Solve { day, .. }
+            | ChallengeError::Io { day, .. }
+            | ChallengeError::TimedOut { day, .. } => *day = Some(challenge_id),
+            ChallengeError::Unregistered { .. } => {}
+        }
+        self
+    }
+}
+
+impl Display for ChallengeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChallengeError::Parse {
+                day,
+                line,
+                column,
+                text,
+                message,
+            } => {
+                if let Some(day) = day {
+                    write!(f, "Day {}, ", day)?;
+                }
+                if let Some(line) = line {
+                    write!(f, "line {}, ", line)?;
+                }
+                write!(f, "column {}: {} (found {:?})", column, message, text)
+            }
+            ChallengeError::Solve { day, message } | ChallengeError::Io { day, message } => {
+                match day {
+                    Some(day) => write!(f, "Day {}: {}", day, message),
+                    None => write!(f, "{}", message),
+                }
+            }
+            ChallengeError::TimedOut {
+                day,
+                timeout,
+                progress,
+            } => {
+                if let Some(day) = day {
+                    write!(f, "Day {}: ", day)?;
+                }
+                write!(f, "Timed out after {:?}", timeout)?;
+                match progress {
+                    Some(progress) => write!(f, " (last progress: {})", progress),
+                    None => Ok(()),
+                }
+            }
+            ChallengeError::Unregistered { year, day } => {
+                let available: Vec<String> = crate::challenge::ChallengeFactory::registered(*year)
+                    .iter()
+                    .map(|day| day.to_string())
+                    .collect();
+                if available.is_empty() {
+                    return write!(f, "No challenges are registered for {}", year);
+                }
+                write!(
+                    f,
+                    "Challenge {} is not registered for {}. Available challenges: {}",
+                    day,
+                    year,
+                    available.join(", ")
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ChallengeError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn error_parse_display() {
+        let error = ChallengeError::parse("x3", 9, "Could not parse cube count")
+            .at_line(3)
+            .with_day(2);
+        assert_eq!(
+            error.to_string(),
+            "Day 2, line 3, column 9: Could not parse cube count (found \"x3\")"
+        );
+    }
+
+    #[test]
+    fn error_offsets() {
+        let error = ChallengeError::parse("x", 2, "Bad")
+            .at_line(1)
+            .offset_lines(4)
+            .offset_columns(6);
+        assert_eq!(error, ChallengeError::parse("x", 8, "Bad").at_line(5));
+    }
+
+    #[test]
+    fn error_unregistered_lists_available() {
+        let error = ChallengeError::Unregistered {
+            year: 2023,
+            day: 25,
+        };
+        assert!(error
+            .to_string()
+            .starts_with("Challenge 25 is not registered for 2023. Available challenges: 1, 2, 3"));
+
+        let error = ChallengeError::Unregistered { year: 2015, day: 1 };
+        assert_eq!(error.to_string(), "No challenges are registered for 2015");
+    }
+
+    #[test]
+    fn error_timed_out_display() {
+        let error = ChallengeError::TimedOut {
+            day: None,
+            timeout: Duration::from_secs(2),
+            progress: Some(3000000),
+        }
+        .with_day(8);
+        assert_eq!(
+            error.to_string(),
+            "Day 8: Timed out after 2s (last progress: 3000000)"
+        );
+    }
+
+    #[test]
+    fn error_solve_display() {
+        let error = ChallengeError::solve("No seeds to map").with_day(5);
+        assert_eq!(error.to_string(), "Day 5: No seeds to map");
+    }
+}
@@ -0,0 +1,265 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    answer::Answer,
+    challenge::DEFAULT_YEAR,
+    error::ChallengeError,
+    runner::{self, RunOptions},
+    utils::parse_toml_table,
+    verify::{self, Outcome},
+};
+
+pub const DEFAULT_EXAMPLES_DIR: &str = "./examples";
+
+// An example is `<day>-<name>.txt` with its expected answers in a sidecar
+// `<day>-<name>.toml`, e.g. `part1 = 142`. Only the listed parts are checked.
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    pub day: u8,
+    pub name: String,
+    pub path: PathBuf,
+    pub expected: BTreeMap<u8, Answer>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ExampleCheck {
+    pub label: String,
+    pub part: Option<u8>,
+    pub outcome: Outcome,
+}
+
+impl ExampleCheck {
+    pub fn is_failure(&self) -> bool {
+        self.outcome != Outcome::Pass
+    }
+}
+
+impl Example {
+    pub fn load(path: &Path) -> Result<Self, ChallengeError> {
+        let (day, name) = parse_file_name(path).ok_or_else(|| {
+            ChallengeError::io(&format!(
+                "Example files must be named <day>-<name>.txt: {}",
+                path.display()
+            ))
+        })?;
+
+        let sidecar = path.with_extension("toml");
+        let content = fs::read_to_string(&sidecar).map_err(|e| {
+            ChallengeError::io(&format!("Could not read {}: {}", sidecar.display(), e))
+        })?;
+        let expected: BTreeMap<u8, Answer> =
+            verify::parse_part_answers(&parse_toml_table(&content)?)?
+                .into_iter()
+                .collect();
+        if expected.is_empty() {
+            return Err(ChallengeError::io(&format!(
+                "{} does not list any part answers",
+                sidecar.display()
+            )));
+        }
+
+        Ok(Example {
+            day,
+            name,
+            path: path.to_path_buf(),
+            expected,
+        })
+    }
+
+    pub fn label(&self) -> String {
+        format!("{}-{}", self.day, self.name)
+    }
+
+    // Solves with `year`'s solver for the example's day.
+    pub fn check(&self, year: u16) -> Vec<ExampleCheck> {
+        let failed = |error: ChallengeError| {
+            self.expected
+                .keys()
+                .map(|part| ExampleCheck {
+                    label: self.label(),
+                    part: Some(*part),
+                    outcome: Outcome::Failed(error.clone()),
+                })
+                .collect()
+        };
+
+        let input = match fs::read(&self.path) {
+            Ok(input) => input,
+            Err(e) => {
+                return failed(ChallengeError::io(&format!(
+                    "Could not read {}: {}",
+                    self.path.display(),
+                    e
+                )))
+            }
+        };
+        // Examples often only make sense for one part, so only solve what is
+        // expected.
+        let options = RunOptions {
+            year,
+            part: match self.expected.keys().collect::<Vec<&u8>>()[..] {
+                [part] => Some(*part),
+                _ => None,
+            },
+            ..RunOptions::default()
+        };
+        let (part_one, part_two) = match runner::solve(self.day, &mut input.as_slice(), &options) {
+            Ok(parts) => parts,
+            Err(error) => return failed(error),
+        };
+
+        self.expected
+            .iter()
+            .map(|(part, expected)| {
+                let result = if *part == 1 { &part_one } else { &part_two };
+                let outcome = match result.as_ref().map(|result| &result.answer) {
+                    Some(Ok(actual)) if actual == expected => Outcome::Pass,
+                    Some(Ok(actual)) => Outcome::Fail {
+                        expected: expected.clone(),
+                        actual: actual.clone(),
+                    },
+                    Some(Err(error)) => Outcome::Failed(error.clone()),
+                    None => Outcome::Missing,
+                };
+                ExampleCheck {
+                    label: self.label(),
+                    part: Some(*part),
+                    outcome,
+                }
+            })
+            .collect()
+    }
+}
+
+fn parse_file_name(path: &Path) -> Option<(u8, String)> {
+    let (day, name) = path.file_stem()?.to_str()?.split_once('-')?;
+    if name.is_empty() {
+        return None;
+    }
+
+    Some((day.parse().ok()?, name.to_string()))
+}
+
+pub fn discover(dir: &Path) -> Result<Vec<PathBuf>, ChallengeError> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| ChallengeError::io(&format!("Could not read {}: {}", dir.display(), e)))?;
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+        .collect();
+    // Order by day, then name, so 10 sorts after 9.
+    paths
+        .sort_by_key(|path| parse_file_name(path).unwrap_or((u8::MAX, path.display().to_string())));
+
+    Ok(paths)
+}
+
+pub fn check_path(path: &Path, year: u16) -> Vec<ExampleCheck> {
+    match Example::load(path) {
+        Ok(example) => example.check(year),
+        Err(error) => vec![ExampleCheck {
+            label: path.display().to_string(),
+            part: None,
+            outcome: Outcome::Failed(error),
+        }],
+    }
+}
+
+// Used by the generated cargo test for each example in examples/, which
+// are all DEFAULT_YEAR's.
+pub fn assert_example(path: &str) {
+    let failures: Vec<String> = check_path(Path::new(path), DEFAULT_YEAR)
+        .into_iter()
+        .filter(ExampleCheck::is_failure)
+        .map(|check| describe(&check))
+        .collect();
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+pub fn test_examples(dir: &Path, year: u16, challenges: &[u8]) -> Result<bool, ChallengeError> {
+    let checks: Vec<ExampleCheck> = discover(dir)?
+        .iter()
+        .filter(|path| parse_file_name(path).is_none_or(|(day, _)| challenges.contains(&day)))
+        .flat_map(|path| check_path(path, year))
+        .collect();
+
+    for check in &checks {
+        println!("{}", describe(check));
+    }
+    let failed = checks.iter().filter(|check| check.is_failure()).count();
+    println!("{} passed, {} failed", checks.len() - failed, failed);
+
+    Ok(failed == 0)
+}
+
+fn describe(check: &ExampleCheck) -> String {
+    let status = match &check.outcome {
+        Outcome::Pass => String::from("pass"),
+        Outcome::Fail { expected, actual } => {
+            format!("FAIL (expected {}, got {})", expected, actual)
+        }
+        Outcome::Failed(error) => format!("FAIL ({})", error),
+        Outcome::Missing => String::from("FAIL (part was not solved)"),
+        Outcome::Recorded | Outcome::Skipped(_) | Outcome::Unverified => String::from("skipped"),
+    };
+    match check.part {
+        Some(part) => format!("{} part {}: {}", check.label, part, status),
+        None => format!("{}: {}", check.label, status),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn examples_parse_file_name() {
+        assert_eq!(
+            parse_file_name(Path::new("examples/10-square-loop.txt")),
+            Some((10, String::from("square-loop")))
+        );
+        assert_eq!(parse_file_name(Path::new("examples/10.txt")), None);
+        assert_eq!(parse_file_name(Path::new("examples/x-loop.txt")), None);
+        assert_eq!(parse_file_name(Path::new("examples/10-.txt")), None);
+    }
+
+    #[test]
+    fn examples_discover_sorts_by_day() {
+        let paths = discover(Path::new(DEFAULT_EXAMPLES_DIR)).unwrap();
+        let days: Vec<u8> = paths
+            .iter()
+            .filter_map(|path| parse_file_name(path).map(|(day, _)| day))
+            .collect();
+
+        assert!(!days.is_empty());
+        assert!(days.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn examples_check_reports_wrong_answer() {
+        let example = Example {
+            day: 6,
+            name: String::from("races"),
+            path: PathBuf::from("examples/6-races.txt"),
+            expected: BTreeMap::from([(1, Answer::from(289))]),
+        };
+
+        assert_eq!(
+            example.check(DEFAULT_YEAR),
+            vec![ExampleCheck {
+                label: String::from("6-races"),
+                part: Some(1),
+                outcome: Outcome::Fail {
+                    expected: Answer::from(289),
+                    actual: Answer::from(288),
+                },
+            }]
+        );
+    }
+}
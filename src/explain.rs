@@ -0,0 +1,84 @@
+use std::{
+    cell::RefCell,
+    fmt,
+    io::{self, Write},
+};
+
+// Receives the derivation of each answer when --explain is on. Solvers write
+// steps in input order and leave out timings, so two runs can be diffed.
+#[derive(Default)]
+pub struct Explain {
+    sink: Option<RefCell<Box<dyn Write>>>,
+}
+
+impl fmt::Debug for Explain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Explain")
+            .field("enabled", &self.enabled())
+            .finish()
+    }
+}
+
+impl Explain {
+    pub fn new(sink: Box<dyn Write>) -> Self {
+        Explain {
+            sink: Some(RefCell::new(sink)),
+        }
+    }
+
+    pub fn stdout() -> Self {
+        Explain::new(Box::new(io::stdout()))
+    }
+
+    // Solvers check this before doing work that only the explanation needs.
+    pub fn enabled(&self) -> bool {
+        self.sink.is_some()
+    }
+
+    pub fn section(&self, title: fmt::Arguments) {
+        self.write(format_args!("{}:", title));
+    }
+
+    pub fn step(&self, step: fmt::Arguments) {
+        self.write(format_args!("  {}", step));
+    }
+
+    fn write(&self, line: fmt::Arguments) {
+        if let Some(sink) = &self.sink {
+            let mut sink = sink.borrow_mut();
+            let _ = writeln!(sink, "{}", line);
+            let _ = sink.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+    use crate::trace::test::Captured;
+
+    pub(crate) fn captured() -> (Explain, Captured) {
+        let captured = Captured::default();
+        (Explain::new(Box::new(captured.clone())), captured)
+    }
+
+    #[test]
+    fn explain_indents_steps_under_sections() {
+        let (explain, captured) = captured();
+        explain.section(format_args!("Day {} part {}", 1, 1));
+        explain.step(format_args!("1abc2: 1 and 2 -> 12"));
+
+        assert_eq!(
+            captured.contents(),
+            "Day 1 part 1:\n  1abc2: 1 and 2 -> 12\n"
+        );
+    }
+
+    #[test]
+    fn explain_is_disabled_by_default() {
+        let explain = Explain::default();
+        explain.step(format_args!("ignored"));
+
+        assert!(!explain.enabled());
+    }
+}
@@ -0,0 +1,78 @@
+use std::{fs, path::Path};
+
+use crate::{client::Client, error::ChallengeError};
+
+pub fn input_url_path(year: u16, day: u8) -> String {
+    format!("/{}/day/{}/input", year, day)
+}
+
+// Downloads a day's input to `path`. An existing input is only replaced with
+// `force`, and is checked before any request is made. Returns the number of
+// bytes written.
+pub fn fetch(
+    client: &Client,
+    year: u16,
+    day: u8,
+    path: &Path,
+    force: bool,
+) -> Result<usize, ChallengeError> {
+    if path.exists() && !force {
+        return Err(ChallengeError::io(&format!(
+            "{} already exists. Use --force to overwrite it",
+            path.display()
+        ))
+        .with_day(day));
+    }
+
+    let input = client
+        .get(&input_url_path(year, day))
+        .map_err(|e| e.with_day(day))?;
+    let error = |e: std::io::Error| {
+        ChallengeError::io(&format!("Could not write {}: {}", path.display(), e)).with_day(day)
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(error)?;
+    }
+    fs::write(path, &input).map_err(error)?;
+
+    Ok(input.len())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        client::test::{client, serve},
+        utils::temp_dir::TempDir,
+    };
+
+    #[test]
+    fn fetch_writes_input() {
+        let (base_url, server) = serve(vec![(200, "Time: 7\nDistance: 9\n")]);
+        let dir = TempDir::new("fetch-writes");
+        let path = dir.join("inputs/2023/6.aoc");
+
+        let written = fetch(&client(&base_url, &dir), 2023, 6, &path, false).unwrap();
+        let requests = server.join().unwrap();
+
+        assert_eq!(written, 20);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "Time: 7\nDistance: 9\n");
+        assert!(requests[0].starts_with("GET /2023/day/6/input "));
+    }
+
+    #[test]
+    fn fetch_refuses_to_overwrite_without_force() {
+        let (base_url, server) = serve(vec![(200, "new")]);
+        let dir = TempDir::new("fetch-force");
+        let path = dir.join("6.aoc");
+        fs::write(&path, "old").unwrap();
+        let client = client(&base_url, &dir);
+
+        assert!(fetch(&client, 2023, 6, &path, false).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+
+        fetch(&client, 2023, 6, &path, true).unwrap();
+        server.join().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+    }
+}
@@ -0,0 +1,31 @@
+// `is_multiple_of` needs Rust 1.87, which is newer than this crate needs otherwise.
+#![allow(clippy::manual_is_multiple_of)]
+
+pub mod answer;
+pub mod bench;
+pub mod cache;
+pub mod challenge;
+pub mod client;
+pub mod config;
+pub mod context;
+pub mod error;
+pub mod examples;
+pub mod explain;
+pub mod fetch;
+pub mod output;
+pub mod repl;
+pub mod runner;
+pub mod submit;
+pub mod trace;
+pub mod utils;
+pub mod verify;
+pub mod watch;
+
+pub use crate::{
+    answer::Answer,
+    challenge::{Challenge, ChallengeFactory},
+    context::SolveContext,
+    error::ChallengeError,
+    explain::Explain,
+    trace::{Trace, TraceLevel},
+};
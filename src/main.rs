@@ -1,49 +1,922 @@
-use crate::challenge::ChallengeFactory;
-use clap::{command, Arg};
-use std::{fs::File, path::Path};
-
-mod challenge;
-mod utils;
+use advent_of_code_2023::{
+    bench::{self, BenchOptions},
+    cache::{self, Cache},
+    client::{self, Client, RateLimiter},
+    config::{self, Config, Settings},
+    examples, fetch,
+    output::{self, Format},
+    repl::{self, Repl},
+    runner::{self, RunStatus},
+    submit::{self, History, Verdict},
+    verify, watch, ChallengeError, ChallengeFactory, Explain, Trace, TraceLevel,
+};
+use clap::{command, Arg, ArgAction, ArgMatches, Command};
+use std::{
+    env,
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+    process,
+    rc::Rc,
+    time::Duration,
+};
 
 fn main() {
-    let matches = command!("Rust - Advent of Code 2023")
-        .version("1.0")
-        .author("WVAviator")
-        .about("Solves input files from Advent of Code 2023")
-        .arg(
-            Arg::new("challenge")
-                .required(true)
-                .index(1)
-                .help("The challenge number from Advent of Code 2023"),
-        )
-        .arg(
-            Arg::new("input")
-                .long("input")
-                .required(false)
-                .help("Optional input file. If not provided, defaults to ./files/<challenge>"),
-        )
-        .get_matches();
+    let matches =
+        command!("Rust - Advent of Code 2023")
+            .version("1.0")
+            .author("WVAviator")
+            .about("Solves input files from Advent of Code 2023")
+            .args_conflicts_with_subcommands(true)
+            .subcommand_negates_reqs(true)
+            .arg(Arg::new("challenge").required(true).index(1).help(
+                "The challenge number from Advent of Code 2023, a range such as 1..=10, or all",
+            ))
+            .arg(
+                Arg::new("input")
+                    .long("input")
+                    .required(false)
+                    .help("Optional input file, or - to read from stdin. If not provided, defaults to ./inputs/<year>/<challenge>.aoc, or the inputs pattern from AOC_INPUTS or aoc.toml"),
+            )
+            .arg(
+                Arg::new("year")
+                    .long("year")
+                    .value_parser(clap::value_parser!(u16))
+                    .help("The Advent of Code year of the challenge. Defaults to $AOC_YEAR, then year in aoc.toml, then 2023"),
+            )
+            .arg(
+                Arg::new("profile")
+                    .long("profile")
+                    .conflicts_with("input")
+                    .help("Read inputs from ./inputs/<year>/<profile>/<challenge>.aoc. Defaults to $AOC_PROFILE, then profile in aoc.toml"),
+            )
+            .arg(
+                Arg::new("all-profiles")
+                    .long("all-profiles")
+                    .action(ArgAction::SetTrue)
+                    .conflicts_with_all(["input", "profile", "watch"])
+                    .help("Solve the challenge for every profile in ./inputs/<year> and compare the answers"),
+            )
+            .arg(
+                Arg::new("part")
+                    .long("part")
+                    .value_parser(["1", "2"])
+                    .help("Only solve the given part"),
+            )
+            .arg(Arg::new("solver").long("solver").help(
+                "Use a specific solver strategy for a single challenge. See the solvers command",
+            ))
+            .arg(
+                Arg::new("format")
+                    .long("format")
+                    .value_parser(Format::NAMES)
+                    .help("Output format. json and csv include timings and input hashes. Defaults to $AOC_FORMAT, then format in aoc.toml, then text"),
+            )
+            .arg(
+                Arg::new("timeout")
+                    .long("timeout")
+                    .value_parser(parse_timeout)
+                    .help("Stop each part after this many seconds, reporting its last progress. Defaults to $AOC_TIMEOUT, then timeout in aoc.toml"),
+            )
+            .arg(
+                Arg::new("trace")
+                    .long("trace")
+                    .value_parser(TraceLevel::NAMES)
+                    .help("Write solver progress and debug output to stderr at this level. Defaults to $AOC_TRACE, then trace in aoc.toml, then off"),
+            )
+            .arg(
+                Arg::new("trace-file")
+                    .long("trace-file")
+                    .help("Write trace output to this file instead of stderr"),
+            )
+            .arg(
+                Arg::new("strict")
+                    .long("strict")
+                    .action(ArgAction::SetTrue)
+                    .help("Report CRLF line endings, a byte order mark or trailing whitespace in the input instead of ignoring them"),
+            )
+            .arg(
+                Arg::new("no-cache")
+                    .long("no-cache")
+                    .action(ArgAction::SetTrue)
+                    .help("Solve every part instead of using answers cached in .aoc-cache"),
+            )
+            .arg(
+                Arg::new("explain")
+                    .long("explain")
+                    .action(ArgAction::SetTrue)
+                    .help("Print how each answer was derived before the answers"),
+            )
+            .arg(
+                Arg::new("watch")
+                    .long("watch")
+                    .action(ArgAction::SetTrue)
+                    .help("Re-run whenever the input file changes"),
+            )
+            .arg(
+                Arg::new("watch-interval")
+                    .long("watch-interval")
+                    .value_parser(clap::value_parser!(u64))
+                    .default_value("500")
+                    .help("How often to check for changes, in milliseconds"),
+            )
+            .arg(
+                Arg::new("examples")
+                    .long("examples")
+                    .requires("watch")
+                    .help("Also watch this examples directory and check its examples on each run"),
+            )
+            .subcommand(Command::new("list").about("Lists the registered challenges"))
+            .subcommand(
+                Command::new("solvers")
+                    .about("Lists the solver strategies available for each challenge"),
+            )
+            .subcommand(
+                Command::new("bench")
+                    .about("Benchmarks a challenge and compares it against a saved baseline")
+                    .arg(
+                        Arg::new("challenge")
+                            .required(true)
+                            .index(1)
+                            .value_parser(clap::value_parser!(u8))
+                            .help("The challenge number to benchmark"),
+                    )
+                    .arg(
+                        Arg::new("year")
+                            .long("year")
+                            .value_parser(clap::value_parser!(u16))
+                            .help("The Advent of Code year of the challenge. Defaults to $AOC_YEAR, then year in aoc.toml, then 2023"),
+                    )
+                    .arg(
+                        Arg::new("input")
+                            .long("input")
+                            .help("Optional input file, or - to read from stdin"),
+                    )
+                    .arg(
+                        Arg::new("part")
+                            .long("part")
+                            .value_parser(["1", "2"])
+                            .help("Only benchmark the given part"),
+                    )
+                    .arg(
+                        Arg::new("solver")
+                            .long("solver")
+                            .help("Use a specific solver strategy"),
+                    )
+                    .arg(
+                        Arg::new("iterations")
+                            .long("iterations")
+                            .value_parser(clap::builder::RangedU64ValueParser::<usize>::new().range(1..))
+                            .default_value("100")
+                            .help("Number of timed runs per part"),
+                    )
+                    .arg(
+                        Arg::new("warmup")
+                            .long("warmup")
+                            .value_parser(clap::value_parser!(usize))
+                            .default_value("10")
+                            .help("Number of untimed runs per part before timing"),
+                    )
+                    .arg(
+                        Arg::new("baseline")
+                            .long("baseline")
+                            .default_value(bench::DEFAULT_BASELINE_PATH)
+                            .help("Baseline file to compare against"),
+                    )
+                    .arg(
+                        Arg::new("save-baseline")
+                            .long("save-baseline")
+                            .action(ArgAction::SetTrue)
+                            .help("Save these results as the new baseline"),
+                    )
+                    .arg(
+                        Arg::new("threshold")
+                            .long("threshold")
+                            .value_parser(clap::value_parser!(f64))
+                            .default_value("10")
+                            .help("Percentage slowdown of the median reported as a regression"),
+                    ),
+            )
+            .subcommand(
+                Command::new("test-examples")
+                    .about("Runs the example inputs and checks them against their expected answers")
+                    .arg(
+                        Arg::new("challenge")
+                            .index(1)
+                            .default_value("all")
+                            .help("The challenge number, a range such as 1..=10, or all"),
+                    )
+                    .arg(
+                        Arg::new("dir")
+                            .long("dir")
+                            .default_value(examples::DEFAULT_EXAMPLES_DIR)
+                            .help("Directory of <day>-<name>.txt examples with <day>-<name>.toml answers"),
+                    )
+                    .arg(
+                        Arg::new("year")
+                            .long("year")
+                            .value_parser(clap::value_parser!(u16))
+                            .help("The Advent of Code year of the examples. Defaults to $AOC_YEAR, then year in aoc.toml, then 2023"),
+                    ),
+            )
+            .subcommand(
+                Command::new("verify")
+                    .about("Checks answers against the stored answers file")
+                    .arg(
+                        Arg::new("challenge")
+                            .index(1)
+                            .default_value("all")
+                            .help("The challenge number, a range such as 1..=10, or all"),
+                    )
+                    .arg(Arg::new("answers").long("answers").help(
+                        "Answers file in plain text or TOML. Defaults to ./answers.toml if it exists, otherwise ./answers.txt",
+                    ))
+                    .arg(
+                        Arg::new("record")
+                            .long("record")
+                            .action(ArgAction::SetTrue)
+                            .help("Record current answers for parts missing from the answers file. Existing entries and comments are kept"),
+                    )
+                    .arg(
+                        Arg::new("year")
+                            .long("year")
+                            .value_parser(clap::value_parser!(u16))
+                            .help("The Advent of Code year of the challenges. Defaults to $AOC_YEAR, then year in aoc.toml, then 2023"),
+                    ),
+            )
+            .subcommand(
+                Command::new("config")
+                    .about("Shows the configuration from flags, environment variables and aoc.toml")
+                    .subcommand_required(true)
+                    .subcommand(
+                        Command::new("show")
+                            .about("Prints each setting with where its value came from")
+                            .arg(
+                                Arg::new("year")
+                                    .long("year")
+                                    .value_parser(clap::value_parser!(u16))
+                                    .help("Show the effective settings with this year flag"),
+                            )
+                            .arg(
+                                Arg::new("profile")
+                                    .long("profile")
+                                    .help("Show the effective settings with this profile flag"),
+                            )
+                            .arg(
+                                Arg::new("format")
+                                    .long("format")
+                                    .value_parser(Format::NAMES)
+                                    .help("Show the effective settings with this format flag"),
+                            )
+                            .arg(
+                                Arg::new("timeout")
+                                    .long("timeout")
+                                    .value_parser(parse_timeout)
+                                    .help("Show the effective settings with this timeout flag"),
+                            )
+                            .arg(
+                                Arg::new("trace")
+                                    .long("trace")
+                                    .value_parser(TraceLevel::NAMES)
+                                    .help("Show the effective settings with this trace flag"),
+                            )
+                            .arg(
+                                Arg::new("base-url")
+                                    .long("base-url")
+                                    .help("Show the effective settings with this base URL flag"),
+                            ),
+                    ),
+            )
+            .subcommand(
+                Command::new("cache")
+                    .about("Manages the answer cache")
+                    .subcommand_required(true)
+                    .subcommand(Command::new("clear").about("Removes every cached answer")),
+            )
+            .subcommand(
+                Command::new("fetch")
+                    .about("Downloads a challenge input into the inputs directory")
+                    .arg(
+                        Arg::new("challenge")
+                            .required(true)
+                            .index(1)
+                            .value_parser(clap::value_parser!(u8))
+                            .help("The challenge number to download"),
+                    )
+                    .arg(
+                        Arg::new("year")
+                            .long("year")
+                            .value_parser(clap::value_parser!(u16))
+                            .help("The Advent of Code year of the challenge. Defaults to $AOC_YEAR, then year in aoc.toml, then 2023"),
+                    )
+                    .arg(
+                        Arg::new("profile")
+                            .long("profile")
+                            .help("Save the input under inputs/<year>/<profile>/ instead"),
+                    )
+                    .arg(
+                        Arg::new("force")
+                            .long("force")
+                            .action(ArgAction::SetTrue)
+                            .help("Overwrite an existing input"),
+                    )
+                    .arg(Arg::new("base-url").long("base-url").help(
+                        "Server to download from. Defaults to $AOC_BASE_URL, then base_url in aoc.toml, then https://adventofcode.com",
+                    )),
+            )
+            .subcommand(
+                Command::new("submit")
+                    .about("Solves a part and submits its answer")
+                    .arg(
+                        Arg::new("challenge")
+                            .required(true)
+                            .index(1)
+                            .value_parser(clap::value_parser!(u8))
+                            .help("The challenge number to submit"),
+                    )
+                    .arg(
+                        Arg::new("part")
+                            .required(true)
+                            .index(2)
+                            .value_parser(["1", "2"])
+                            .help("The part to submit"),
+                    )
+                    .arg(
+                        Arg::new("year")
+                            .long("year")
+                            .value_parser(clap::value_parser!(u16))
+                            .help("The Advent of Code year of the challenge. Defaults to $AOC_YEAR, then year in aoc.toml, then 2023"),
+                    )
+                    .arg(
+                        Arg::new("input")
+                            .long("input")
+                            .help("Optional input file, or - to read from stdin"),
+                    )
+                    .arg(
+                        Arg::new("solver")
+                            .long("solver")
+                            .help("Use a specific solver strategy"),
+                    )
+                    .arg(Arg::new("base-url").long("base-url").help(
+                        "Server to submit to. Defaults to $AOC_BASE_URL, then base_url in aoc.toml, then https://adventofcode.com",
+                    )),
+            )
+            .subcommand(
+                Command::new("repl")
+                    .about("Loads a challenge once and accepts commands and day-specific queries")
+                    .arg(
+                        Arg::new("challenge")
+                            .required(true)
+                            .index(1)
+                            .value_parser(clap::value_parser!(u8))
+                            .help("The challenge number to load"),
+                    )
+                    .arg(
+                        Arg::new("year")
+                            .long("year")
+                            .value_parser(clap::value_parser!(u16))
+                            .help("The Advent of Code year of the challenge. Defaults to $AOC_YEAR, then year in aoc.toml, then 2023"),
+                    )
+                    .arg(
+                        Arg::new("input")
+                            .long("input")
+                            .help("Optional input file"),
+                    )
+                    .arg(
+                        Arg::new("solver")
+                            .long("solver")
+                            .help("Use a specific solver strategy"),
+                    )
+                    .arg(
+                        Arg::new("timeout")
+                            .long("timeout")
+                            .value_parser(parse_timeout)
+                            .help("Stop each part after this many seconds. Defaults to $AOC_TIMEOUT, then timeout in aoc.toml"),
+                    ),
+            )
+            .get_matches();
+
+    let config = Config::load(Path::new(config::CONFIG_PATH))
+        .unwrap_or_else(|e| exit_with_error(format!("{}: {}", config::CONFIG_PATH, e)));
+    // Flags are read from whichever command was run, e.g. `config show`.
+    let mut command_matches = &matches;
+    while let Some((_, subcommand)) = command_matches.subcommand() {
+        command_matches = subcommand;
+    }
+    let settings = resolve_settings(command_matches, &config);
+
+    if let Some(("config", config_matches)) = matches.subcommand() {
+        if let Some(("show", _)) = config_matches.subcommand() {
+            println!("{}", settings.show());
+        }
+        return;
+    }
+
+    if let Some(("list", _)) = matches.subcommand() {
+        for year in ChallengeFactory::years() {
+            println!("{}", year);
+            for challenge in ChallengeFactory::registered(year) {
+                let registration = ChallengeFactory::registration(year, &challenge)
+                    .expect("Registered challenges have a registration.");
+                println!("{:>2} {}", registration.day, registration.title);
+            }
+        }
+        return;
+    }
+
+    if let Some(("solvers", _)) = matches.subcommand() {
+        for year in ChallengeFactory::years() {
+            println!("{}", year);
+            for challenge in ChallengeFactory::registered(year) {
+                let solvers = ChallengeFactory::solvers(year, &challenge).unwrap_or_default();
+                let mut listed: Vec<String> =
+                    solvers.iter().map(|solver| solver.to_string()).collect();
+                if listed.len() > 1 {
+                    listed[0].push_str(" (default)");
+                }
+                println!("Day {:>2}: {}", challenge, listed.join(", "));
+            }
+        }
+        return;
+    }
+
+    if let Some(("bench", bench_matches)) = matches.subcommand() {
+        let challenge = *bench_matches
+            .get_one::<u8>("challenge")
+            .expect("Challenge is required.");
+        let year = year(&settings);
+        let default_input_path = default_input_path(&settings, year, challenge);
+        let input = bench_matches
+            .get_one::<String>("input")
+            .unwrap_or(&default_input_path);
+        let run_options = runner::RunOptions {
+            year,
+            part: bench_matches
+                .get_one::<String>("part")
+                .map(|part| part.parse().expect("Part is validated by clap.")),
+            solver: bench_matches.get_one::<String>("solver").cloned(),
+            ..runner::RunOptions::default()
+        };
+        let options = BenchOptions {
+            warmup: *bench_matches
+                .get_one::<usize>("warmup")
+                .expect("Has a default."),
+            iterations: *bench_matches
+                .get_one::<usize>("iterations")
+                .expect("Has a default."),
+            threshold: *bench_matches
+                .get_one::<f64>("threshold")
+                .expect("Has a default."),
+        };
+        let baseline_path = bench_matches
+            .get_one::<String>("baseline")
+            .expect("Has a default.");
+
+        match bench::bench(
+            challenge,
+            input,
+            &run_options,
+            &options,
+            Path::new(baseline_path),
+            bench_matches.get_flag("save-baseline"),
+        ) {
+            Ok(true) => return,
+            Ok(false) => process::exit(1),
+            Err(e) => exit_with_error(e),
+        }
+    }
+
+    if let Some(("test-examples", examples_matches)) = matches.subcommand() {
+        let year = year(&settings);
+        let challenges = parse_challenges(
+            year,
+            examples_matches
+                .get_one::<String>("challenge")
+                .expect("Challenge has a default value."),
+        );
+        let dir = examples_matches
+            .get_one::<String>("dir")
+            .expect("Dir has a default value.");
+
+        match examples::test_examples(Path::new(dir), year, &challenges) {
+            Ok(true) => return,
+            Ok(false) => process::exit(1),
+            Err(e) => exit_with_error(e),
+        }
+    }
+
+    if let Some(("verify", verify_matches)) = matches.subcommand() {
+        let year = year(&settings);
+        let selection = verify_matches
+            .get_one::<String>("challenge")
+            .expect("Challenge has a default value.");
+        let challenges = (selection != "all").then(|| parse_challenges(year, selection));
+        let answers_path = verify_matches
+            .get_one::<String>("answers")
+            .map(String::as_str)
+            .unwrap_or_else(|| verify::default_answers_path());
+        let record = verify_matches.get_flag("record");
+        let options = runner::RunOptions {
+            year,
+            inputs: inputs(&settings),
+            timeout: setting(&settings, "timeout", |timeout| parse_timeout(timeout).ok()),
+            ..runner::RunOptions::default()
+        };
+
+        match verify::verify(challenges, &options, Path::new(answers_path), record) {
+            Ok(true) => return,
+            Ok(false) => process::exit(1),
+            Err(e) => exit_with_error(format!("{}: {}", answers_path, e)),
+        }
+    }
+
+    if let Some(("cache", cache_matches)) = matches.subcommand() {
+        if let Some(("clear", _)) = cache_matches.subcommand() {
+            let cache = Cache::new(Path::new(cache::DEFAULT_CACHE_DIR));
+            match cache.clear() {
+                Ok(count) => println!(
+                    "Removed {} cached answers from {}",
+                    count,
+                    cache.dir().display()
+                ),
+                Err(e) => exit_with_error(e),
+            }
+        }
+        return;
+    }
+
+    if let Some(("fetch", fetch_matches)) = matches.subcommand() {
+        let challenge = *fetch_matches
+            .get_one::<u8>("challenge")
+            .expect("Challenge is required.");
+        let year = year(&settings);
+        let path = default_input_path(&settings, year, challenge);
+        let client = open_client(&settings);
+
+        match fetch::fetch(
+            &client,
+            year,
+            challenge,
+            Path::new(&path),
+            fetch_matches.get_flag("force"),
+        ) {
+            Ok(bytes) => println!("Saved {} bytes to {}", bytes, path),
+            Err(e) => exit_with_error(e),
+        }
+        return;
+    }
+
+    if let Some(("submit", submit_matches)) = matches.subcommand() {
+        let challenge = *submit_matches
+            .get_one::<u8>("challenge")
+            .expect("Challenge is required.");
+        let part: u8 = submit_matches
+            .get_one::<String>("part")
+            .expect("Part is required.")
+            .parse()
+            .expect("Part is validated by clap.");
+        let year = year(&settings);
+        let default_input_path = default_input_path(&settings, year, challenge);
+        let input = submit_matches
+            .get_one::<String>("input")
+            .unwrap_or(&default_input_path);
+        let options = runner::RunOptions {
+            year,
+            part: Some(part),
+            solver: submit_matches.get_one::<String>("solver").cloned(),
+            ..runner::RunOptions::default()
+        };
+
+        let answer = match runner::run_input(challenge, input, &options).status {
+            RunStatus::Solved(part_one, part_two) => {
+                let result = if part == 1 { part_one } else { part_two };
+                result
+                    .expect("The selected part is solved.")
+                    .answer
+                    .unwrap_or_else(|e| exit_with_error(e))
+            }
+            RunStatus::MissingInput(path) => exit_with_error(format!(
+                "Could not find input file at specified path.\nProvided path: {}",
+                path
+            )),
+            RunStatus::NotImplemented => exit_with_error(ChallengeError::Unregistered {
+                year,
+                day: challenge,
+            }),
+            RunStatus::Failed(e) => exit_with_error(e),
+        };
+
+        let client = open_client(&settings);
+        let mut history =
+            History::load(&Path::new(client::DEFAULT_STATE_DIR).join(submit::HISTORY_FILE))
+                .unwrap_or_else(|e| exit_with_error(e));
+        match submit::submit(&client, &mut history, year, challenge, part, &answer) {
+            Ok(response) => {
+                println!(
+                    "Day {} part {}: {} is {}",
+                    challenge, part, answer, response.verdict
+                );
+                println!("{}", response.message);
+                if response.verdict != Verdict::Correct {
+                    process::exit(1);
+                }
+            }
+            Err(e) => exit_with_error(e),
+        }
+        return;
+    }
+
+    if let Some(("repl", repl_matches)) = matches.subcommand() {
+        let challenge = *repl_matches
+            .get_one::<u8>("challenge")
+            .expect("Challenge is required.");
+        let year = year(&settings);
+        let default_input_path = default_input_path(&settings, year, challenge);
+        let input = repl_matches
+            .get_one::<String>("input")
+            .unwrap_or(&default_input_path);
+        let options = runner::RunOptions {
+            year,
+            solver: repl_matches.get_one::<String>("solver").cloned(),
+            timeout: setting(&settings, "timeout", |timeout| parse_timeout(timeout).ok()),
+            ..runner::RunOptions::default()
+        };
+
+        let mut session = match Repl::open(challenge, input, options) {
+            Ok(session) => session,
+            Err(e) => exit_with_error(e),
+        };
+        if let Err(e) = repl::repl(&mut session, &mut io::stdin().lock(), &mut io::stdout()) {
+            exit_with_error(e);
+        }
+        return;
+    }
 
     let challenge = matches
         .get_one::<String>("challenge")
         .expect("You must provide a valid challenge number.\nCorrect usage: ./aoc2023 <challenge>");
-    let challenge = challenge.parse::<u8>().expect(&format!(
-        "Challenge number must be a valid number.\nProvided challenge: {}",
-        challenge
-    ));
-    let default_input_path = format!("./inputs/{}.aoc", challenge);
+
+    let year = year(&settings);
+    let inputs = inputs(&settings);
+    let options = runner::RunOptions {
+        year,
+        inputs: inputs.clone(),
+        part: matches
+            .get_one::<String>("part")
+            .map(|part| part.parse().expect("Part is validated by clap.")),
+        solver: matches.get_one::<String>("solver").cloned(),
+        timeout: setting(&settings, "timeout", |timeout| parse_timeout(timeout).ok()),
+        trace: Rc::new(open_trace(
+            setting(&settings, "trace", TraceLevel::from).expect("Trace has a default."),
+            matches.get_one::<String>("trace-file"),
+        )),
+        explain: Rc::new(if matches.get_flag("explain") {
+            Explain::stdout()
+        } else {
+            Explain::default()
+        }),
+        strict: matches.get_flag("strict"),
+        cache: (!matches.get_flag("no-cache"))
+            .then(|| Cache::new(Path::new(cache::DEFAULT_CACHE_DIR))),
+    };
+    let format = setting(&settings, "format", Format::from).expect("Format has a default.");
+    if options.explain.enabled() && format != Format::Text {
+        exit_with_error("--explain can only be used with the text format.");
+    }
+    let default_input_path = |challenge: u8| default_input_path(&settings, year, challenge);
+
+    if matches.get_flag("all-profiles") {
+        let challenges = parse_challenges(year, challenge);
+        if challenges.len() > 1 && options.solver.is_some() {
+            exit_with_error("--solver can only be used with a single challenge.");
+        }
+        let dir = runner::inputs_dir(&inputs, year);
+        let profiles = runner::profiles(&dir).unwrap_or_else(|e| exit_with_error(e));
+        if profiles.is_empty() {
+            exit_with_error(format!(
+                "No profiles found. Put each profile's inputs in a subdirectory of {}",
+                dir.display()
+            ));
+        }
+
+        let rows: Vec<runner::RunRow> = challenges
+            .iter()
+            .flat_map(|challenge| {
+                profiles
+                    .iter()
+                    .map(|profile| runner::run_profile(*challenge, profile, &options))
+                    .collect::<Vec<runner::RunRow>>()
+            })
+            .collect();
+        output::print(&rows, format);
+        if rows.iter().any(runner::RunRow::is_failure) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    if matches.get_flag("watch") {
+        let challenges = parse_challenges(year, challenge);
+        if challenges.len() > 1 && options.solver.is_some() {
+            exit_with_error("--solver can only be used with a single challenge.");
+        }
+        let input = matches.get_one::<String>("input");
+        if input.is_some_and(|input| input == "-") {
+            exit_with_error("--watch needs an input file and cannot read from stdin.");
+        }
+        let inputs: Vec<(u8, String)> = challenges
+            .iter()
+            .map(|challenge| {
+                let path = match input {
+                    Some(input) if challenges.len() == 1 => input.clone(),
+                    _ => default_input_path(*challenge),
+                };
+                (*challenge, path)
+            })
+            .collect();
+        let examples_dir = matches.get_one::<String>("examples");
+        let mut paths: Vec<PathBuf> = inputs.iter().map(|(_, path)| PathBuf::from(path)).collect();
+        paths.extend(examples_dir.map(PathBuf::from));
+        let interval = Duration::from_millis(
+            *matches
+                .get_one::<u64>("watch-interval")
+                .expect("Watch interval has a default value."),
+        );
+
+        watch::watch(paths, interval, |paths| {
+            watch::clear_screen();
+            let rows: Vec<runner::RunRow> = inputs
+                .iter()
+                .map(|(challenge, path)| runner::run_input(*challenge, path, &options))
+                .collect();
+            output::print(&rows, format);
+            if let Some(dir) = examples_dir {
+                println!();
+                if let Err(e) = examples::test_examples(Path::new(dir), year, &challenges) {
+                    eprintln!("Error: {}", e);
+                }
+            }
+            let watched: Vec<String> = paths
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect();
+            println!(
+                "\nWatching {} for changes. Press Ctrl+C to stop.",
+                watched.join(", ")
+            );
+        });
+    }
+
+    if let Some(challenges) = runner::parse_selection(year, challenge) {
+        if options.solver.is_some() {
+            exit_with_error("--solver can only be used with a single challenge.");
+        }
+        let rows: Vec<runner::RunRow> = challenges
+            .into_iter()
+            .map(|challenge| runner::run_input(challenge, &default_input_path(challenge), &options))
+            .collect();
+        output::print(&rows, format);
+        if rows.iter().any(runner::RunRow::is_failure) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    let challenge = challenge.parse::<u8>().unwrap_or_else(|_| {
+        exit_with_error(format!(
+            "Challenge number must be a valid number.\nProvided challenge: {}",
+            challenge
+        ))
+    });
+    let default_input_path = default_input_path(challenge);
     let input = matches
         .get_one::<String>("input")
         .unwrap_or(&default_input_path);
 
-    let path = Path::new(input);
-    let file = File::open(&path).expect(&format!(
-        "Could not find input file at specified path.\nProvided path: {}",
-        path.display()
-    ));
+    let row = runner::run_input(challenge, input, &options);
+    if format != Format::Text {
+        output::print(std::slice::from_ref(&row), format);
+        if row.is_failure() || !matches!(row.status, RunStatus::Solved(..)) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    match row.status {
+        RunStatus::Solved(part_one, part_two) => {
+            for (label, part) in [("Part One", part_one), ("Part Two", part_two)] {
+                if let Some(part) = part {
+                    let cached = if part.cached { " (cached)" } else { "" };
+                    let answer = part.answer.unwrap_or_else(|e| exit_with_error(e));
+                    println!("{}: {}{}", label, answer, cached);
+                }
+            }
+        }
+        RunStatus::MissingInput(path) => exit_with_error(format!(
+            "Could not find input file at specified path.\nProvided path: {}",
+            path
+        )),
+        RunStatus::NotImplemented => exit_with_error(ChallengeError::Unregistered {
+            year,
+            day: challenge,
+        }),
+        RunStatus::Failed(e) => exit_with_error(e),
+    }
+}
+
+fn parse_challenges(year: u16, selection: &str) -> Vec<u8> {
+    runner::parse_selection(year, selection)
+        .or_else(|| {
+            selection
+                .parse::<u8>()
+                .ok()
+                .map(|challenge| vec![challenge])
+        })
+        .unwrap_or_else(|| {
+            exit_with_error(format!(
+                "Challenge must be a number, a range, or all.\nProvided challenge: {}",
+                selection
+            ))
+        })
+}
+
+fn parse_timeout(value: &str) -> Result<Duration, String> {
+    value
+        .parse::<f64>()
+        .ok()
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .ok_or_else(|| String::from("Timeout must be a non-negative number of seconds"))
+}
+
+fn open_trace(level: TraceLevel, path: Option<&String>) -> Trace {
+    if path.is_some() && level == TraceLevel::Off {
+        exit_with_error("--trace-file needs a trace level from --trace, AOC_TRACE or aoc.toml.");
+    }
+    match path {
+        Some(path) => Trace::file(level, Path::new(path))
+            .unwrap_or_else(|e| exit_with_error(format!("Could not open {}: {}", path, e))),
+        None => Trace::stderr(level),
+    }
+}
+
+fn resolve_settings(matches: &ArgMatches, config: &Config) -> Settings {
+    let flag = |name: &str| {
+        matches
+            .try_get_raw(name)
+            .ok()
+            .flatten()
+            .and_then(|mut values| values.next())
+            .and_then(|value| value.to_str())
+            .map(String::from)
+    };
 
-    let solution = ChallengeFactory::create(&challenge, &file);
+    Settings::resolve(flag, |name| env::var(name).ok(), config)
+}
+
+fn setting<T>(settings: &Settings, name: &str, parse: impl FnOnce(&str) -> Option<T>) -> Option<T> {
+    settings
+        .parse(name, parse)
+        .unwrap_or_else(|e| exit_with_error(e))
+}
+
+fn year(settings: &Settings) -> u16 {
+    setting(settings, "year", |year| year.parse().ok()).expect("Year has a default.")
+}
+
+// The pattern needs {day}, or every day would read the same file.
+fn inputs(settings: &Settings) -> String {
+    setting(settings, "inputs", |inputs| {
+        inputs.contains("{day}").then(|| inputs.to_string())
+    })
+    .expect("Inputs has a default.")
+}
+
+// Where a challenge's input is read from, or saved to by fetch, when no
+// --input is given. A profile from --profile, the environment or aoc.toml
+// picks that profile's directory. Every command resolves inputs this way.
+fn default_input_path(settings: &Settings, year: u16, challenge: u8) -> String {
+    let inputs = inputs(settings);
+    match settings.get("profile") {
+        Some(profile) => runner::profile_input_path(&inputs, year, profile, challenge),
+        None => runner::input_path(&inputs, year, challenge),
+    }
+}
+
+fn open_client(settings: &Settings) -> Client {
+    let session = client::session(settings).unwrap_or_else(|e| exit_with_error(e));
+    let base_url = settings.get("base_url").expect("Base URL has a default.");
+
+    Client::new(
+        base_url,
+        &session,
+        RateLimiter::new(
+            Path::new(client::DEFAULT_STATE_DIR),
+            client::DEFAULT_REQUEST_INTERVAL,
+        ),
+    )
+}
 
-    println!("Part One: {}", solution.solve_part_one());
-    println!("Part Two: {}", solution.solve_part_two());
+fn exit_with_error(error: impl Display) -> ! {
+    eprintln!("Error: {}", error);
+    process::exit(1);
 }
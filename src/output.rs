@@ -0,0 +1,552 @@
+use crate::{
+    answer::Answer,
+    error::ChallengeError,
+    runner::{self, PartResult, RunRow, RunStatus},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+impl Format {
+    pub const NAMES: [&'static str; 3] = ["text", "json", "csv"];
+
+    pub fn from(name: &str) -> Option<Self> {
+        match name {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ErrorRecord {
+    pub kind: &'static str,
+    pub message: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub text: Option<String>,
+}
+
+impl ErrorRecord {
+    pub fn from(error: &ChallengeError) -> Self {
+        match error {
+            ChallengeError::Parse {
+                line,
+                column,
+                text,
+                message,
+                ..
+            } => ErrorRecord {
+                kind: "parse",
+                message: message.clone(),
+                line: *line,
+                column: Some(*column),
+                text: Some(text.clone()),
+            },
+            ChallengeError::Solve { message, .. } => ErrorRecord::message("solve", message),
+            ChallengeError::Io { message, .. } => ErrorRecord::message("io", message),
+            ChallengeError::TimedOut { .. } => ErrorRecord::message("timeout", &error.to_string()),
+            ChallengeError::Unregistered { .. } => {
+                ErrorRecord::message("unregistered", &error.to_string())
+            }
+        }
+    }
+
+    fn message(kind: &'static str, message: &str) -> Self {
+        ErrorRecord {
+            kind,
+            message: message.to_string(),
+            line: None,
+            column: None,
+            text: None,
+        }
+    }
+}
+
+// One record per solved part. Days that fail before any part is solved get a
+// single record without a part.
+#[derive(Debug, PartialEq)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub profile: Option<String>,
+    pub part: Option<u8>,
+    pub answer: Option<Answer>,
+    pub elapsed_ns: Option<u128>,
+    pub cached: bool,
+    pub input_path: Option<String>,
+    pub input_hash: Option<String>,
+    pub error: Option<ErrorRecord>,
+}
+
+impl Record {
+    pub fn status(&self) -> &'static str {
+        match (&self.answer, &self.error) {
+            (_, Some(_)) => "error",
+            (Some(Answer::NotImplemented), None) => "not_implemented",
+            _ => "solved",
+        }
+    }
+}
+
+pub fn records(rows: &[RunRow]) -> Vec<Record> {
+    let mut records = Vec::new();
+    for row in rows {
+        let record = |part: Option<u8>| Record {
+            year: row.year,
+            day: row.challenge,
+            profile: row.profile.clone(),
+            part,
+            answer: None,
+            elapsed_ns: None,
+            cached: false,
+            input_path: row.input.as_ref().map(|input| input.path.clone()),
+            input_hash: row.input.as_ref().map(|input| input.hash.clone()),
+            error: None,
+        };
+
+        match &row.status {
+            RunStatus::Solved(part_one, part_two) => {
+                for (part, result) in [(1, part_one), (2, part_two)] {
+                    if let Some(PartResult {
+                        answer,
+                        elapsed,
+                        cached,
+                    }) = result
+                    {
+                        let (answer, error) = match answer {
+                            Ok(answer) => (Some(answer.clone()), None),
+                            Err(error) => (None, Some(ErrorRecord::from(error))),
+                        };
+                        records.push(Record {
+                            answer,
+                            // Cached answers were not timed.
+                            elapsed_ns: (!cached).then_some(elapsed.as_nanos()),
+                            cached: *cached,
+                            error,
+                            ..record(Some(part))
+                        });
+                    }
+                }
+            }
+            RunStatus::MissingInput(path) => records.push(Record {
+                input_path: Some(path.clone()),
+                error: Some(ErrorRecord::message(
+                    "missing_input",
+                    &format!("Could not find input file at {}", path),
+                )),
+                ..record(None)
+            }),
+            RunStatus::NotImplemented => records.push(Record {
+                error: Some(ErrorRecord::from(&ChallengeError::Unregistered {
+                    year: row.year,
+                    day: row.challenge,
+                })),
+                ..record(None)
+            }),
+            RunStatus::Failed(error) => records.push(Record {
+                error: Some(ErrorRecord::from(error)),
+                ..record(None)
+            }),
+        }
+    }
+
+    records
+}
+
+pub fn print(rows: &[RunRow], format: Format) {
+    match format {
+        Format::Text => {
+            runner::print_table(rows);
+            runner::print_errors(rows);
+        }
+        Format::Json => println!("{}", to_json(&records(rows))),
+        Format::Csv => println!("{}", to_csv(&records(rows))),
+    }
+}
+
+pub fn to_json(records: &[Record]) -> String {
+    if records.is_empty() {
+        return String::from("[]");
+    }
+
+    let objects: Vec<String> = records
+        .iter()
+        .map(|record| {
+            let error = match &record.error {
+                Some(error) => json_object(&[
+                    ("kind", json_string(error.kind)),
+                    ("message", json_string(&error.message)),
+                    ("line", json_option(error.line, |line| line.to_string())),
+                    (
+                        "column",
+                        json_option(error.column, |column| column.to_string()),
+                    ),
+                    ("text", json_option(error.text.as_deref(), json_string)),
+                ]),
+                None => String::from("null"),
+            };
+            // Every record has the same keys, whatever the run included.
+            let fields = [
+                ("year", record.year.to_string()),
+                ("day", record.day.to_string()),
+                (
+                    "profile",
+                    json_option(record.profile.as_deref(), json_string),
+                ),
+                ("part", json_option(record.part, |part| part.to_string())),
+                ("status", json_string(record.status())),
+                ("answer", json_option(record.answer.as_ref(), json_answer)),
+                (
+                    "elapsed_ns",
+                    json_option(record.elapsed_ns, |elapsed| elapsed.to_string()),
+                ),
+                ("cached", record.cached.to_string()),
+                (
+                    "input",
+                    json_option(record.input_path.as_deref(), json_string),
+                ),
+                (
+                    "input_hash",
+                    json_option(record.input_hash.as_deref(), json_string),
+                ),
+                ("error", error),
+            ];
+            format!("  {}", json_object(&fields))
+        })
+        .collect();
+
+    format!("[\n{}\n]", objects.join(",\n"))
+}
+
+pub fn to_csv(records: &[Record]) -> String {
+    let headers = [
+        "year",
+        "day",
+        "profile",
+        "part",
+        "status",
+        "answer",
+        "elapsed_ns",
+        "cached",
+        "input",
+        "input_hash",
+        "error_kind",
+        "error",
+    ];
+    let mut lines = vec![headers.join(",")];
+    for record in records {
+        let fields = [
+            record.year.to_string(),
+            record.day.to_string(),
+            record.profile.clone().unwrap_or_default(),
+            record.part.map(|part| part.to_string()).unwrap_or_default(),
+            record.status().to_string(),
+            record
+                .answer
+                .as_ref()
+                .filter(|answer| answer.is_implemented())
+                .map(|answer| answer.to_string())
+                .unwrap_or_default(),
+            record
+                .elapsed_ns
+                .map(|elapsed| elapsed.to_string())
+                .unwrap_or_default(),
+            record.cached.to_string(),
+            record.input_path.clone().unwrap_or_default(),
+            record.input_hash.clone().unwrap_or_default(),
+            record
+                .error
+                .as_ref()
+                .map(|error| error.kind.to_string())
+                .unwrap_or_default(),
+            record
+                .error
+                .as_ref()
+                .map(|error| error.message.clone())
+                .unwrap_or_default(),
+        ];
+        let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        lines.push(fields.join(","));
+    }
+
+    lines.join("\n")
+}
+
+fn json_object(fields: &[(&str, String)]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(key, value)| format!("{}:{}", json_string(key), value))
+        .collect();
+    format!("{{{}}}", fields.join(","))
+}
+
+fn json_option<T>(value: Option<T>, to_json: impl Fn(T) -> String) -> String {
+    value.map(to_json).unwrap_or_else(|| String::from("null"))
+}
+
+// JSON readers often parse numbers as doubles, so integers past 2^53 - 1 are
+// written as strings to keep every digit.
+fn json_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Integer(value) if value.magnitude().bits() > 53 => json_string(&value.to_string()),
+        Answer::Integer(value) => value.to_string(),
+        Answer::Text(text) => json_string(text),
+        Answer::NotImplemented => String::from("null"),
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use num::BigInt;
+
+    use super::*;
+    use crate::runner::InputInfo;
+
+    fn get_rows() -> Vec<RunRow> {
+        vec![
+            RunRow {
+                year: 2023,
+                challenge: 2,
+                profile: None,
+                input: Some(InputInfo {
+                    path: String::from("./inputs/2.aoc"),
+                    hash: String::from("00000000000000ff"),
+                }),
+                status: RunStatus::Solved(
+                    Some(PartResult {
+                        answer: Ok(Answer::from(8)),
+                        elapsed: Duration::from_nanos(1500),
+                        cached: false,
+                    }),
+                    Some(PartResult {
+                        answer: Err(ChallengeError::solve("Bad \"game\"").with_day(2)),
+                        elapsed: Duration::from_nanos(20),
+                        cached: false,
+                    }),
+                ),
+            },
+            RunRow {
+                year: 2023,
+                challenge: 3,
+                profile: None,
+                input: Some(InputInfo {
+                    path: String::from("./inputs/3.aoc"),
+                    hash: String::from("0000000000000001"),
+                }),
+                status: RunStatus::Failed(
+                    ChallengeError::parse("x", 4, "Expected a part number")
+                        .at_line(2)
+                        .with_day(3),
+                ),
+            },
+            RunRow {
+                year: 2023,
+                challenge: 25,
+                profile: None,
+                input: None,
+                status: RunStatus::NotImplemented,
+            },
+        ]
+    }
+
+    #[test]
+    fn output_records() {
+        let records = records(&get_rows());
+
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[0].answer, Some(Answer::from(8)));
+        assert_eq!(records[0].elapsed_ns, Some(1500));
+        assert_eq!(records[1].error.as_ref().unwrap().kind, "solve");
+        assert_eq!(records[2].part, None);
+        assert_eq!(records[2].error.as_ref().unwrap().line, Some(2));
+        assert_eq!(records[3].error.as_ref().unwrap().kind, "unregistered");
+    }
+
+    #[test]
+    fn output_json() {
+        let json = to_json(&records(&get_rows()[..2]));
+
+        assert_eq!(
+            json,
+            [
+                "[",
+                "  {\"year\":2023,\"day\":2,\"profile\":null,\"part\":1,\"status\":\"solved\",\"answer\":8,\"elapsed_ns\":1500,\"cached\":false,\"input\":\"./inputs/2.aoc\",\"input_hash\":\"00000000000000ff\",\"error\":null},",
+                "  {\"year\":2023,\"day\":2,\"profile\":null,\"part\":2,\"status\":\"error\",\"answer\":null,\"elapsed_ns\":20,\"cached\":false,\"input\":\"./inputs/2.aoc\",\"input_hash\":\"00000000000000ff\",\"error\":{\"kind\":\"solve\",\"message\":\"Bad \\\"game\\\"\",\"line\":null,\"column\":null,\"text\":null}},",
+                "  {\"year\":2023,\"day\":3,\"profile\":null,\"part\":null,\"status\":\"error\",\"answer\":null,\"elapsed_ns\":null,\"cached\":false,\"input\":\"./inputs/3.aoc\",\"input_hash\":\"0000000000000001\",\"error\":{\"kind\":\"parse\",\"message\":\"Expected a part number\",\"line\":2,\"column\":4,\"text\":\"x\"}}",
+                "]",
+            ]
+            .join("\n")
+        );
+        assert_eq!(to_json(&[]), "[]");
+    }
+
+    #[test]
+    fn output_csv() {
+        let csv = to_csv(&records(&get_rows()[..1]));
+
+        assert_eq!(
+            csv,
+            [
+                "year,day,profile,part,status,answer,elapsed_ns,cached,input,input_hash,error_kind,error",
+                "2023,2,,1,solved,8,1500,false,./inputs/2.aoc,00000000000000ff,,",
+                "2023,2,,2,error,,20,false,./inputs/2.aoc,00000000000000ff,solve,\"Bad \"\"game\"\"\"",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn output_profiles() {
+        let mut rows = get_rows();
+        rows.truncate(1);
+        rows[0].profile = Some(String::from("alice"));
+        let records = records(&rows);
+
+        assert!(to_json(&records[..1])
+            .starts_with("[\n  {\"year\":2023,\"day\":2,\"profile\":\"alice\",\"part\":1,"));
+        assert_eq!(
+            to_csv(&records[..1]),
+            [
+                "year,day,profile,part,status,answer,elapsed_ns,cached,input,input_hash,error_kind,error",
+                "2023,2,alice,1,solved,8,1500,false,./inputs/2.aoc,00000000000000ff,,",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn output_cached_answers() {
+        let mut rows = get_rows();
+        rows.truncate(1);
+        if let RunStatus::Solved(Some(part), _) = &mut rows[0].status {
+            part.cached = true;
+        }
+        let records = records(&rows);
+
+        assert_eq!(records[0].elapsed_ns, None);
+        assert!(to_json(&records[..1])
+            .contains("\"elapsed_ns\":null,\"cached\":true,\"input\":\"./inputs/2.aoc\""));
+        assert_eq!(
+            to_csv(&records),
+            [
+                "year,day,profile,part,status,answer,elapsed_ns,cached,input,input_hash,error_kind,error",
+                "2023,2,,1,solved,8,,true,./inputs/2.aoc,00000000000000ff,,",
+                "2023,2,,2,error,,20,false,./inputs/2.aoc,00000000000000ff,solve,\"Bad \"\"game\"\"\"",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn output_schema_does_not_depend_on_cache() {
+        let cold = records(&get_rows()[..1]);
+        let mut rows = get_rows();
+        rows.truncate(1);
+        if let RunStatus::Solved(Some(part), _) = &mut rows[0].status {
+            part.cached = true;
+        }
+        let warm = records(&rows);
+
+        assert_eq!(
+            to_csv(&cold).lines().next(),
+            Some("year,day,profile,part,status,answer,elapsed_ns,cached,input,input_hash,error_kind,error")
+        );
+        assert_eq!(to_csv(&cold).lines().next(), to_csv(&warm).lines().next());
+        assert!(to_json(&cold).contains("\"profile\":null,"));
+        // Only the values of a cached answer differ, not the keys.
+        assert_eq!(
+            to_json(&cold).replacen(
+                "\"elapsed_ns\":1500,\"cached\":false",
+                "\"elapsed_ns\":null,\"cached\":true",
+                1
+            ),
+            to_json(&warm)
+        );
+    }
+
+    #[test]
+    fn output_not_implemented_answer() {
+        let rows = [RunRow {
+            year: 2023,
+            challenge: 10,
+            profile: None,
+            input: None,
+            status: RunStatus::Solved(
+                None,
+                Some(PartResult {
+                    answer: Ok(Answer::NotImplemented),
+                    elapsed: Duration::ZERO,
+                    cached: false,
+                }),
+            ),
+        }];
+        let records = records(&rows);
+
+        assert_eq!(records[0].status(), "not_implemented");
+        assert_eq!(
+            to_csv(&records).lines().nth(1),
+            Some("2023,10,,2,not_implemented,,0,false,,,,")
+        );
+        assert!(to_json(&records).contains("\"answer\":null"));
+    }
+
+    #[test]
+    fn output_json_large_answers() {
+        let safe = BigInt::from(2u64.pow(53) - 1);
+        let large = BigInt::from(2u64.pow(53));
+
+        assert_eq!(
+            json_answer(&Answer::Integer(safe.clone())),
+            "9007199254740991"
+        );
+        assert_eq!(json_answer(&Answer::Integer(-safe)), "-9007199254740991");
+        assert_eq!(
+            json_answer(&Answer::Integer(large.clone())),
+            "\"9007199254740992\""
+        );
+        assert_eq!(
+            json_answer(&Answer::Integer(-large)),
+            "\"-9007199254740992\""
+        );
+    }
+
+    #[test]
+    fn output_json_string_escapes() {
+        assert_eq!(json_string("a\\b\n\u{1}"), "\"a\\\\b\\n\\u0001\"");
+    }
+}
@@ -0,0 +1,325 @@
+use std::{
+    io::{self, BufRead, Write},
+    time::Instant,
+};
+
+use crate::{
+    challenge::{Challenge, ChallengeFactory},
+    error::ChallengeError,
+    runner::{self, RunOptions},
+    utils::read_normalized,
+};
+
+const COMMANDS: [&str; 7] = [
+    "part1",
+    "part2",
+    "reload",
+    "input <path>",
+    "line <n>",
+    "help",
+    "quit",
+];
+
+#[derive(Debug, PartialEq)]
+pub enum Reply {
+    Output(String),
+    Quit,
+}
+
+// Keeps one challenge loaded so it can be solved and queried repeatedly
+// without re-reading the input.
+pub struct Repl {
+    day: u8,
+    path: String,
+    input: String,
+    challenge: Box<dyn Challenge>,
+    options: RunOptions,
+}
+
+impl Repl {
+    pub fn open(day: u8, path: &str, options: RunOptions) -> Result<Self, ChallengeError> {
+        let (input, challenge) = Repl::load(day, path, &options)?;
+        Ok(Repl {
+            day,
+            path: path.to_string(),
+            input,
+            challenge,
+            options,
+        })
+    }
+
+    fn load(
+        day: u8,
+        path: &str,
+        options: &RunOptions,
+    ) -> Result<(String, Box<dyn Challenge>), ChallengeError> {
+        // Commands are read from stdin, so it cannot also be the input.
+        if path == "-" {
+            return Err(
+                ChallengeError::io("The REPL cannot read its input from stdin.").with_day(day),
+            );
+        }
+        let input = runner::read_input(path).map_err(|e| {
+            ChallengeError::io(&format!("Could not read {}: {}", path, e)).with_day(day)
+        })?;
+        let input = read_normalized(&mut input.as_slice())?;
+        let mut challenge = ChallengeFactory::create(options.year, &day, &mut input.as_bytes())?;
+        if let Some(solver) = &options.solver {
+            challenge.use_solver(solver)?;
+        }
+
+        Ok((input, challenge))
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    pub fn execute(&mut self, command: &str) -> Result<Reply, ChallengeError> {
+        let words: Vec<&str> = command.split_whitespace().collect();
+        let (command, args) = match words.split_first() {
+            Some((command, args)) => (*command, args),
+            None => return Ok(Reply::Output(String::new())),
+        };
+
+        let output = match (command, args) {
+            ("quit" | "exit", _) => return Ok(Reply::Quit),
+            ("help", _) => self.help(),
+            ("part1", _) => self.solve(1)?,
+            ("part2", _) => self.solve(2)?,
+            ("reload", _) => self.open_input(self.path.clone())?,
+            ("input", [path]) => self.open_input(path.to_string())?,
+            ("input", _) => return Err(ChallengeError::solve("Usage: input <path>")),
+            ("line", [number]) => self.line(number)?,
+            ("line", _) => return Err(ChallengeError::solve("Usage: line <n>")),
+            _ if self.query_names().contains(&command) => self.challenge.query(command, args)?,
+            _ => {
+                let mut available: Vec<&str> = COMMANDS.to_vec();
+                available.extend(self.query_names());
+                return Err(ChallengeError::unknown_command(command, &available));
+            }
+        };
+
+        Ok(Reply::Output(output))
+    }
+
+    fn query_names(&self) -> Vec<&'static str> {
+        self.challenge
+            .queries()
+            .into_iter()
+            .filter_map(|usage| usage.split_whitespace().next())
+            .collect()
+    }
+
+    fn help(&self) -> String {
+        let mut commands: Vec<&str> = COMMANDS.to_vec();
+        commands.extend(self.challenge.queries());
+        commands.join("\n")
+    }
+
+    fn solve(&self, part: u8) -> Result<String, ChallengeError> {
+        let context = self.options.context();
+        let start = Instant::now();
+        let answer = if part == 1 {
+            self.challenge.solve_part_one(&context)
+        } else {
+            self.challenge.solve_part_two(&context)
+        }
+        .map_err(|e| e.with_day(self.day))?;
+        let label = if part == 1 { "Part One" } else { "Part Two" };
+
+        Ok(format!("{}: {} ({:.2?})", label, answer, start.elapsed()))
+    }
+
+    // The current input is kept if the new one cannot be loaded.
+    fn open_input(&mut self, path: String) -> Result<String, ChallengeError> {
+        let (input, challenge) = Repl::load(self.day, &path, &self.options)?;
+        self.input = input;
+        self.challenge = challenge;
+        let output = format!("Loaded {} ({} lines)", path, self.input.lines().count());
+        self.path = path;
+
+        Ok(output)
+    }
+
+    fn line(&self, number: &str) -> Result<String, ChallengeError> {
+        let line = number
+            .parse::<usize>()
+            .ok()
+            .and_then(|number| self.input.lines().nth(number.checked_sub(1)?))
+            .ok_or_else(|| {
+                ChallengeError::solve(&format!(
+                    "Line must be between 1 and {}",
+                    self.input.lines().count()
+                ))
+            })?;
+
+        match self.challenge.parse_line(line) {
+            Some(parsed) => Ok(format!("{}\n{}", line, parsed?)),
+            None => Ok(format!(
+                "{}\nDay {} has no line parser, as its lines only make sense together.",
+                line, self.day
+            )),
+        }
+    }
+}
+
+pub fn repl(repl: &mut Repl, input: &mut dyn BufRead, output: &mut dyn Write) -> io::Result<()> {
+    let mut command = String::new();
+    loop {
+        write!(output, "day {}> ", repl.day())?;
+        output.flush()?;
+        command.clear();
+        if input.read_line(&mut command)? == 0 {
+            return Ok(());
+        }
+
+        match repl.execute(&command) {
+            Ok(Reply::Output(text)) if text.is_empty() => {}
+            Ok(Reply::Output(text)) => writeln!(output, "{}", text)?,
+            Ok(Reply::Quit) => return Ok(()),
+            Err(error) => writeln!(output, "Error: {}", error)?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::temp_dir::TempDir;
+    use std::time::Duration;
+
+    fn open(day: u8, path: &str) -> Repl {
+        Repl::open(day, path, RunOptions::default()).unwrap()
+    }
+
+    fn output(repl: &mut Repl, command: &str) -> String {
+        match repl.execute(command).unwrap() {
+            Reply::Output(text) => text,
+            Reply::Quit => panic!("Unexpected quit"),
+        }
+    }
+
+    #[test]
+    fn repl_solves_parts() {
+        let mut repl = open(7, "examples/7-hands.txt");
+
+        assert!(output(&mut repl, "part1").starts_with("Part One: 6440 ("));
+        assert!(output(&mut repl, "part2").starts_with("Part Two: 5905 ("));
+        assert_eq!(repl.execute("quit").unwrap(), Reply::Quit);
+    }
+
+    #[test]
+    fn repl_day_specific_queries() {
+        let mut repl = open(5, "examples/5-almanac.txt");
+        assert_eq!(
+            output(&mut repl, "map seed 79"),
+            "seed 79 -> soil 81 -> fertilizer 81 -> water 81 -> light 74 -> temperature 78 -> humidity 78 -> location 82"
+        );
+
+        let mut repl = open(8, "examples/8-network.txt");
+        assert_eq!(output(&mut repl, "travel AAA RL"), "AAA -R-> CCC -L-> ZZZ");
+        assert!(output(&mut repl, "help").ends_with("travel <node> <directions>"));
+    }
+
+    #[test]
+    fn repl_shows_parsed_line() {
+        let mut repl = open(7, "examples/7-hands.txt");
+
+        assert_eq!(
+            output(&mut repl, "line 1"),
+            "32T3K 765\nHand { cards: [C3, C2, CT, C3, CK], hand_type: OnePair, bid: 765 }"
+        );
+        assert!(repl.execute("line 0").is_err());
+        assert!(repl.execute("line 99").is_err());
+    }
+
+    #[test]
+    fn repl_shows_parsed_line_for_each_line_day() {
+        let line = |day: u8, path: &str, number: &str| {
+            output(&mut open(day, path), &format!("line {}", number))
+        };
+
+        assert_eq!(
+            line(1, "examples/1-spelled-digits.txt", "1"),
+            "two1nine\nDigits: 1 and 1\nDigits and words: 2 and 9"
+        );
+        assert_eq!(
+            line(4, "examples/4-cards.txt", "1"),
+            "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53\nCard 1: 4 matches (83, 86, 17, 48)"
+        );
+        assert_eq!(
+            line(6, "examples/6-races.txt", "2"),
+            "Distance:  9  40  200\nDistance: [9, 40, 200], combined 940200"
+        );
+        assert_eq!(
+            line(9, "examples/9-histories.txt", "3"),
+            "10 13 16 21 30 45\nPrevious: 5, next: 68"
+        );
+        assert!(line(3, "examples/3-schematic.txt", "1")
+            .ends_with("Day 3 has no line parser, as its lines only make sense together."));
+    }
+
+    #[test]
+    fn repl_switches_input() {
+        let mut repl = open(8, "examples/8-network.txt");
+
+        assert_eq!(
+            output(&mut repl, "input examples/8-repeat.txt"),
+            "Loaded examples/8-repeat.txt (5 lines)"
+        );
+        assert!(output(&mut repl, "part1").starts_with("Part One: 6 ("));
+        assert!(repl.execute("input examples/missing.txt").is_err());
+        assert!(output(&mut repl, "reload").starts_with("Loaded examples/8-repeat.txt"));
+    }
+
+    #[test]
+    fn repl_rejects_stdin_input() {
+        assert!(Repl::open(7, "-", RunOptions::default()).is_err());
+
+        let mut repl = open(7, "examples/7-hands.txt");
+        assert!(repl.execute("input -").is_err());
+        assert!(output(&mut repl, "part1").starts_with("Part One: 6440 ("));
+    }
+
+    #[test]
+    fn repl_solves_within_timeout() {
+        let options = RunOptions {
+            solver: Some(String::from("brute-force")),
+            timeout: Some(Duration::ZERO),
+            ..RunOptions::default()
+        };
+        let dir = TempDir::new("repl-timeout");
+        let path = dir.join("8.txt");
+        // The two ghosts never stand on __Z nodes at the same time.
+        std::fs::write(
+            &path,
+            "L\n\n11A = (11Z, 11Z)\n11Z = (11A, 11A)\n22A = (22B, 22B)\n22B = (22A, 22A)\n",
+        )
+        .unwrap();
+        let mut repl = Repl::open(8, path.to_str().unwrap(), options).unwrap();
+
+        assert!(matches!(
+            repl.execute("part2"),
+            Err(ChallengeError::TimedOut { .. })
+        ));
+    }
+
+    #[test]
+    fn repl_session() {
+        let mut repl = open(7, "examples/7-hands.txt");
+        let mut output = Vec::new();
+
+        super::repl(
+            &mut repl,
+            &mut "hand 32T3K\n\nbogus\nquit\n".as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "day 7> 32T3K: OnePair (OnePair with jokers)\nday 7> day 7> Error: Unknown command 'bogus'. \
+             Available commands: part1, part2, reload, input <path>, line <n>, help, quit, hand\nday 7> "
+        );
+    }
+}
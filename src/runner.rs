@@ -0,0 +1,719 @@
+use std::{
+    fs,
+    io::{self, BufRead, Read},
+    path::{Path, PathBuf},
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    answer::Answer,
+    cache::{Cache, CacheKey},
+    challenge::{ChallengeFactory, DEFAULT_YEAR},
+    context::SolveContext,
+    error::ChallengeError,
+    explain::Explain,
+    trace::Trace,
+    utils::{input_hash, read_strict},
+};
+
+pub struct PartResult {
+    pub answer: Result<Answer, ChallengeError>,
+    pub elapsed: Duration,
+    // Read from the answer cache instead of solved.
+    pub cached: bool,
+}
+
+pub enum RunStatus {
+    Solved(Option<PartResult>, Option<PartResult>),
+    MissingInput(String),
+    NotImplemented,
+    Failed(ChallengeError),
+}
+
+#[derive(Clone)]
+pub struct RunOptions {
+    // Which year's registry and inputs to use.
+    pub year: u16,
+    pub part: Option<u8>,
+    pub solver: Option<String>,
+    // Input path pattern, see DEFAULT_INPUT_PATTERN.
+    pub inputs: String,
+    // Time budget for each part. Solvers that check their context stop once
+    // it is spent.
+    pub timeout: Option<Duration>,
+    // Shared by every solve so all parts and days write to the same sink.
+    pub trace: Rc<Trace>,
+    pub explain: Rc<Explain>,
+    // Reject inputs that would otherwise be normalized, e.g. CRLF line endings.
+    pub strict: bool,
+    // Answers are looked up here before solving, and stored once solved.
+    pub cache: Option<Cache>,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        RunOptions {
+            year: DEFAULT_YEAR,
+            inputs: DEFAULT_INPUT_PATTERN.to_string(),
+            part: None,
+            solver: None,
+            timeout: None,
+            trace: Rc::default(),
+            explain: Rc::default(),
+            strict: false,
+            cache: None,
+        }
+    }
+}
+
+impl RunOptions {
+    pub fn includes_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|selected| selected == part)
+    }
+
+    pub fn context(&self) -> SolveContext {
+        self.timeout
+            .map(SolveContext::with_timeout)
+            .unwrap_or_default()
+            .with_trace(Rc::clone(&self.trace))
+            .with_explain(Rc::clone(&self.explain))
+    }
+}
+
+pub struct InputInfo {
+    pub path: String,
+    pub hash: String,
+}
+
+pub struct RunRow {
+    pub year: u16,
+    pub challenge: u8,
+    // Set when the input came from `inputs/<year>/<profile>/`.
+    pub profile: Option<String>,
+    pub input: Option<InputInfo>,
+    pub status: RunStatus,
+}
+
+// Where inputs are read from, with `{year}` and `{day}` filled in. Each
+// profile has a subdirectory next to the inputs, e.g.
+// `inputs/<year>/<profile>/<day>.aoc`.
+pub const DEFAULT_INPUT_PATTERN: &str = "./inputs/{year}/{day}.aoc";
+
+pub fn input_path(pattern: &str, year: u16, challenge: u8) -> String {
+    pattern
+        .replace("{year}", &year.to_string())
+        .replace("{day}", &challenge.to_string())
+}
+
+pub fn default_input_path(year: u16, challenge: u8) -> String {
+    input_path(DEFAULT_INPUT_PATTERN, year, challenge)
+}
+
+pub fn profile_input_path(pattern: &str, year: u16, profile: &str, challenge: u8) -> String {
+    let path = PathBuf::from(input_path(pattern, year, challenge));
+    let file_name = path.file_name().unwrap_or_default().to_os_string();
+    path.with_file_name(profile)
+        .join(file_name)
+        .display()
+        .to_string()
+}
+
+// The directory holding a year's inputs and its profile directories.
+pub fn inputs_dir(pattern: &str, year: u16) -> PathBuf {
+    let path = PathBuf::from(input_path(pattern, year, 1));
+    path.parent().map(Path::to_path_buf).unwrap_or_default()
+}
+
+// Each subdirectory of the inputs directory holds one account's inputs.
+pub fn profiles(dir: &Path) -> Result<Vec<String>, ChallengeError> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| ChallengeError::io(&format!("Could not read {}: {}", dir.display(), e)))?;
+
+    let mut profiles: Vec<String> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir())
+        .filter_map(|path| path.file_name()?.to_str().map(String::from))
+        .collect();
+    profiles.sort();
+
+    Ok(profiles)
+}
+
+pub fn read_input(path: &str) -> io::Result<Vec<u8>> {
+    if path == "-" {
+        let mut input = Vec::new();
+        io::stdin().lock().read_to_end(&mut input)?;
+        return Ok(input);
+    }
+
+    fs::read(path)
+}
+
+pub fn parse_selection(year: u16, selection: &str) -> Option<Vec<u8>> {
+    if selection == "all" {
+        return Some(ChallengeFactory::registered(year));
+    }
+
+    let (start, end) = match selection.split_once("..=") {
+        Some((start, end)) => (start, end.parse::<u8>().ok()?),
+        None => {
+            let (start, end) = selection.split_once("..")?;
+            (start, end.parse::<u8>().ok()?.checked_sub(1)?)
+        }
+    };
+    let start = start.parse::<u8>().ok()?;
+
+    Some((start..=end).collect())
+}
+
+pub fn run(challenge: u8, options: &RunOptions) -> RunRow {
+    run_input(
+        challenge,
+        &input_path(&options.inputs, options.year, challenge),
+        options,
+    )
+}
+
+pub fn run_profile(challenge: u8, profile: &str, options: &RunOptions) -> RunRow {
+    RunRow {
+        profile: Some(profile.to_string()),
+        ..run_input(
+            challenge,
+            &profile_input_path(&options.inputs, options.year, profile, challenge),
+            options,
+        )
+    }
+}
+
+pub fn run_input(challenge: u8, input_path: &str, options: &RunOptions) -> RunRow {
+    if !ChallengeFactory::is_registered(options.year, &challenge) {
+        return RunRow {
+            year: options.year,
+            challenge,
+            profile: None,
+            input: None,
+            status: RunStatus::NotImplemented,
+        };
+    }
+
+    let input = match read_input(input_path) {
+        Ok(input) => input,
+        Err(e) => {
+            // Only an absent file is a missing input. Anything else, such as
+            // a permission error, is a failure worth reporting.
+            let status = if e.kind() == io::ErrorKind::NotFound {
+                RunStatus::MissingInput(input_path.to_string())
+            } else {
+                RunStatus::Failed(
+                    ChallengeError::io(&format!("Could not read {}: {}", input_path, e))
+                        .with_day(challenge),
+                )
+            };
+            return RunRow {
+                year: options.year,
+                challenge,
+                profile: None,
+                input: None,
+                status,
+            };
+        }
+    };
+    let solved = match &options.cache {
+        // Explanations come from the solve itself, so they bypass the cache.
+        Some(cache) if !options.explain.enabled() => {
+            solve_cached(challenge, &input, options, cache)
+        }
+        _ => solve(challenge, &mut input.as_slice(), options),
+    };
+    let status = match solved {
+        Ok((part_one, part_two)) => RunStatus::Solved(part_one, part_two),
+        Err(error) => RunStatus::Failed(error),
+    };
+
+    RunRow {
+        year: options.year,
+        challenge,
+        profile: None,
+        input: Some(InputInfo {
+            path: input_path.to_string(),
+            hash: input_hash(&input),
+        }),
+        status,
+    }
+}
+
+pub fn solve(
+    challenge: u8,
+    reader: &mut dyn BufRead,
+    options: &RunOptions,
+) -> Result<(Option<PartResult>, Option<PartResult>), ChallengeError> {
+    let mut solution = if options.strict {
+        let input = read_strict(reader).map_err(|e| e.with_day(challenge))?;
+        ChallengeFactory::create(options.year, &challenge, &mut input.as_bytes())?
+    } else {
+        ChallengeFactory::create(options.year, &challenge, reader)?
+    };
+    if let Some(solver) = &options.solver {
+        solution
+            .use_solver(solver)
+            .map_err(|e| e.with_day(challenge))?;
+    }
+
+    Ok((
+        options.includes_part(1).then(|| {
+            options
+                .explain
+                .section(format_args!("Day {} part 1", challenge));
+            time_part(|| {
+                solution
+                    .solve_part_one(&options.context())
+                    .map_err(|e| e.with_day(challenge))
+            })
+        }),
+        options.includes_part(2).then(|| {
+            options
+                .explain
+                .section(format_args!("Day {} part 2", challenge));
+            time_part(|| {
+                solution
+                    .solve_part_two(&options.context())
+                    .map_err(|e| e.with_day(challenge))
+            })
+        }),
+    ))
+}
+
+// Solves only the selected parts that are not already cached, and caches the
+// answers that were solved. Unimplemented parts are not cached, so they are
+// solved once they have been written.
+fn solve_cached(
+    challenge: u8,
+    input: &[u8],
+    options: &RunOptions,
+    cache: &Cache,
+) -> Result<(Option<PartResult>, Option<PartResult>), ChallengeError> {
+    if options.strict {
+        read_strict(&mut &input[..]).map_err(|e| e.with_day(challenge))?;
+    }
+
+    let hash = input_hash(input);
+    let key = |part: u8| {
+        CacheKey::new(
+            options.year,
+            challenge,
+            part,
+            options.solver.as_deref(),
+            &hash,
+        )
+    };
+    let cached = |part: u8| {
+        options
+            .includes_part(part)
+            .then(|| key(part).and_then(|key| cache.get(&key)))
+            .flatten()
+    };
+    let (cached_one, cached_two) = (cached(1), cached(2));
+
+    let missing: Vec<u8> = [(1, &cached_one), (2, &cached_two)]
+        .into_iter()
+        .filter(|(part, cached)| options.includes_part(*part) && cached.is_none())
+        .map(|(part, _)| part)
+        .collect();
+    let (part_one, part_two) = match missing[..] {
+        [] => (None, None),
+        [part] => solve(
+            challenge,
+            &mut &input[..],
+            &RunOptions {
+                part: Some(part),
+                ..options.clone()
+            },
+        )?,
+        _ => solve(challenge, &mut &input[..], options)?,
+    };
+
+    for (part, result) in [(1, &part_one), (2, &part_two)] {
+        if let (Some(key), Some(Ok(answer))) = (key(part), result.as_ref().map(|r| &r.answer)) {
+            if !answer.is_implemented() {
+                continue;
+            }
+            if let Err(e) = cache.put(&key, answer) {
+                options.trace.debug(format_args!(
+                    "Could not cache day {} part {}: {}",
+                    challenge, part, e
+                ));
+            }
+        }
+    }
+
+    let from_cache = |answer: Answer| PartResult {
+        answer: Ok(answer),
+        elapsed: Duration::ZERO,
+        cached: true,
+    };
+    Ok((
+        part_one.or_else(|| cached_one.map(from_cache)),
+        part_two.or_else(|| cached_two.map(from_cache)),
+    ))
+}
+
+impl RunRow {
+    pub fn is_failure(&self) -> bool {
+        match &self.status {
+            RunStatus::Solved(part_one, part_two) => [part_one, part_two]
+                .into_iter()
+                .flatten()
+                .any(|part| part.answer.is_err()),
+            RunStatus::Failed(_) => true,
+            RunStatus::MissingInput(_) | RunStatus::NotImplemented => false,
+        }
+    }
+}
+
+fn time_part(solve: impl FnOnce() -> Result<Answer, ChallengeError>) -> PartResult {
+    let start = Instant::now();
+    let answer = solve();
+    PartResult {
+        answer,
+        elapsed: start.elapsed(),
+        cached: false,
+    }
+}
+
+pub fn print_table(rows: &[RunRow]) {
+    // Rows from several profiles get a column naming the profile, so the same
+    // day can be compared across accounts.
+    let profiles = rows.iter().any(|row| row.profile.is_some());
+    let cells: Vec<(Vec<String>, Vec<String>)> = rows
+        .iter()
+        .map(|row| {
+            let cells = match &row.status {
+                RunStatus::Solved(part_one, part_two) => [part_one, part_two]
+                    .into_iter()
+                    .flat_map(|part| match part {
+                        Some(part) if part.cached => {
+                            [answer_cell(&part.answer), String::from("cached")]
+                        }
+                        Some(part) => [answer_cell(&part.answer), format!("{:.2?}", part.elapsed)],
+                        None => [String::from("-"), String::from("-")],
+                    })
+                    .collect(),
+                RunStatus::MissingInput(path) => vec![format!("missing input ({})", path)],
+                RunStatus::NotImplemented => vec![String::from("not implemented")],
+                RunStatus::Failed(error) => vec![format!("error: {}", error)],
+            };
+            let mut keys = vec![row.challenge.to_string()];
+            if profiles {
+                keys.push(row.profile.clone().unwrap_or_default());
+            }
+            (keys, cells)
+        })
+        .collect();
+
+    let mut headers = vec!["Day"];
+    if profiles {
+        headers.push("Profile");
+    }
+    let key_count = headers.len();
+    headers.extend(["Part One", "Time", "Part Two", "Time"]);
+    let mut widths: Vec<usize> = headers.iter().map(|header| header.len()).collect();
+    for (keys, row_cells) in &cells {
+        for (i, key) in keys.iter().enumerate() {
+            widths[i] = widths[i].max(key.chars().count());
+        }
+        if row_cells.len() == 1 {
+            continue;
+        }
+        for (i, cell) in row_cells.iter().enumerate() {
+            widths[key_count + i] = widths[key_count + i].max(cell.chars().count());
+        }
+    }
+
+    let header: Vec<String> = headers
+        .iter()
+        .zip(&widths)
+        .map(|(header, width)| format!("{:<width$}", header, width = width))
+        .collect();
+    println!("{}", header.join(" | ").trim_end());
+    let divider: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+    println!("{}", divider.join("-+-"));
+
+    for (keys, row_cells) in cells {
+        let mut line = vec![format!("{:>width$}", keys[0], width = widths[0])];
+        line.extend(
+            keys[1..]
+                .iter()
+                .zip(&widths[1..])
+                .map(|(key, width)| format!("{:<width$}", key, width = width)),
+        );
+        if row_cells.len() == 1 {
+            line.extend(row_cells);
+        } else {
+            line.extend(
+                row_cells
+                    .into_iter()
+                    .zip(&widths[key_count..])
+                    .map(|(cell, width)| format!("{:<width$}", cell, width = width)),
+            );
+        }
+        println!("{}", line.join(" | ").trim_end());
+    }
+}
+
+fn answer_cell(answer: &Result<Answer, ChallengeError>) -> String {
+    match answer {
+        Ok(answer) => answer.to_string(),
+        Err(_) => String::from("error"),
+    }
+}
+
+pub fn print_errors(rows: &[RunRow]) {
+    for row in rows {
+        match &row.status {
+            RunStatus::Solved(part_one, part_two) => {
+                for error in [part_one, part_two]
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|part| part.answer.as_ref().err())
+                {
+                    eprintln!("Error: {}", error);
+                }
+            }
+            RunStatus::Failed(error) => eprintln!("Error: {}", error),
+            RunStatus::MissingInput(_) | RunStatus::NotImplemented => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::temp_dir::TempDir;
+
+    #[test]
+    fn runner_parse_selection_all() {
+        assert_eq!(
+            parse_selection(2023, "all"),
+            Some(ChallengeFactory::registered(2023))
+        );
+    }
+
+    #[test]
+    fn runner_parse_selection_inclusive_range() {
+        assert_eq!(parse_selection(2023, "3..=5"), Some(vec![3, 4, 5]));
+    }
+
+    #[test]
+    fn runner_parse_selection_exclusive_range() {
+        assert_eq!(parse_selection(2023, "3..5"), Some(vec![3, 4]));
+    }
+
+    #[test]
+    fn runner_parse_selection_single_day() {
+        assert_eq!(parse_selection(2023, "3"), None);
+        assert_eq!(parse_selection(2023, "three..=5"), None);
+    }
+
+    #[test]
+    fn runner_run_unregistered_day() {
+        let row = run(25, &RunOptions::default());
+        assert!(matches!(row.status, RunStatus::NotImplemented));
+        assert!(!row.is_failure());
+    }
+
+    #[test]
+    fn runner_row_with_error_is_failure() {
+        let row = RunRow {
+            year: 2023,
+            challenge: 5,
+            profile: None,
+            input: None,
+            status: RunStatus::Solved(
+                Some(PartResult {
+                    answer: Ok(Answer::from(35)),
+                    elapsed: Duration::ZERO,
+                    cached: false,
+                }),
+                Some(PartResult {
+                    answer: Err(ChallengeError::solve("No seeds to map")),
+                    elapsed: Duration::ZERO,
+                    cached: false,
+                }),
+            ),
+        };
+        assert!(row.is_failure());
+    }
+
+    #[test]
+    fn runner_solve_selected_part_with_solver() {
+        let options = RunOptions {
+            part: Some(2),
+            solver: Some(String::from("brute-force")),
+            ..RunOptions::default()
+        };
+        let input = "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\nXXX = (XXX, XXX)";
+
+        let (part_one, part_two) = solve(8, &mut input.as_bytes(), &options).unwrap();
+        assert!(part_one.is_none());
+        assert_eq!(part_two.unwrap().answer, Ok(Answer::from(2)));
+    }
+
+    #[test]
+    fn runner_solve_strict_rejects_crlf() {
+        let options = RunOptions {
+            strict: true,
+            ..RunOptions::default()
+        };
+        let input = "Time: 7\r\nDistance: 9\r\n";
+
+        let error = solve(6, &mut input.as_bytes(), &options).err().unwrap();
+        assert_eq!(
+            error,
+            ChallengeError::parse("\r", 8, "Line ends with CRLF")
+                .at_line(1)
+                .with_day(6)
+        );
+        assert!(solve(6, &mut input.as_bytes(), &RunOptions::default()).is_ok());
+    }
+
+    #[test]
+    fn runner_profiles_lists_directories() {
+        let dir = TempDir::new("runner-profiles");
+        for profile in ["bob", "alice"] {
+            fs::create_dir_all(dir.join(profile)).unwrap();
+        }
+        fs::write(dir.join("1.aoc"), "1abc2").unwrap();
+
+        assert_eq!(profiles(&dir).unwrap(), vec!["alice", "bob"]);
+    }
+
+    #[test]
+    fn runner_unreadable_input_is_a_failure() {
+        let dir = TempDir::new("runner-unreadable");
+        let path = dir.to_string_lossy().to_string();
+
+        let row = run_input(1, &path, &RunOptions::default());
+
+        assert!(matches!(
+            row.status,
+            RunStatus::Failed(ChallengeError::Io { day: Some(1), .. })
+        ));
+        assert!(matches!(
+            run_input(1, &format!("{}/1.aoc", path), &RunOptions::default()).status,
+            RunStatus::MissingInput(_)
+        ));
+    }
+
+    #[test]
+    fn runner_run_profile_reads_profile_input() {
+        let row = run_profile(1, "nobody", &RunOptions::default());
+
+        assert_eq!(row.profile.as_deref(), Some("nobody"));
+        assert!(matches!(
+            row.status,
+            RunStatus::MissingInput(path) if path == "./inputs/2023/nobody/1.aoc"
+        ));
+    }
+
+    #[test]
+    fn runner_run_unregistered_year() {
+        let options = RunOptions {
+            year: 2015,
+            ..RunOptions::default()
+        };
+        let row = run(1, &options);
+
+        assert_eq!(row.year, 2015);
+        assert!(matches!(row.status, RunStatus::NotImplemented));
+        assert_eq!(default_input_path(2015, 1), "./inputs/2015/1.aoc");
+    }
+
+    #[test]
+    fn runner_input_path_patterns() {
+        let pattern = "data/{year}/day{day}/input.txt";
+
+        assert_eq!(input_path(pattern, 2022, 7), "data/2022/day7/input.txt");
+        assert_eq!(
+            profile_input_path(pattern, 2022, "alice", 7),
+            "data/2022/day7/alice/input.txt"
+        );
+        assert_eq!(
+            profile_input_path(DEFAULT_INPUT_PATTERN, 2023, "alice", 7),
+            "./inputs/2023/alice/7.aoc"
+        );
+        assert_eq!(
+            inputs_dir(DEFAULT_INPUT_PATTERN, 2023),
+            PathBuf::from("./inputs/2023")
+        );
+    }
+
+    #[test]
+    fn runner_run_input_uses_cache() {
+        let dir = TempDir::new("runner-cache");
+        let options = RunOptions {
+            part: Some(1),
+            cache: Some(Cache::new(&dir)),
+            ..RunOptions::default()
+        };
+        let cached = |row: &RunRow| match &row.status {
+            RunStatus::Solved(part_one, part_two) => [part_one, part_two]
+                .map(|part| part.as_ref().map(|part| (part.answer.clone(), part.cached))),
+            _ => panic!("Expected a solved row"),
+        };
+
+        let row = run_input(6, "examples/6-races.txt", &options);
+        assert_eq!(cached(&row), [Some((Ok(Answer::from(288)), false)), None]);
+        let row = run_input(6, "examples/6-races.txt", &options);
+        assert_eq!(cached(&row), [Some((Ok(Answer::from(288)), true)), None]);
+
+        // Only the part that was not cached yet is solved.
+        let options = RunOptions {
+            part: None,
+            ..options
+        };
+        let row = run_input(6, "examples/6-races.txt", &options);
+        assert_eq!(
+            cached(&row),
+            [
+                Some((Ok(Answer::from(288)), true)),
+                Some((Ok(Answer::from(71503)), false))
+            ]
+        );
+    }
+
+    #[test]
+    fn runner_run_input_skips_caching_not_implemented() {
+        let dir = TempDir::new("runner-cache-not-implemented");
+        let cache = Cache::new(&dir);
+        let options = RunOptions {
+            part: Some(2),
+            cache: Some(cache.clone()),
+            ..RunOptions::default()
+        };
+
+        let row = run_input(10, "examples/10-square-loop.txt", &options);
+        match &row.status {
+            RunStatus::Solved(None, Some(part)) => {
+                assert_eq!(part.answer, Ok(Answer::NotImplemented));
+                assert!(!part.cached);
+            }
+            _ => panic!("Expected part two to be solved"),
+        }
+        let input = read_input("examples/10-square-loop.txt").unwrap();
+        let key = CacheKey::new(2023, 10, 2, None, &input_hash(&input)).unwrap();
+        assert_eq!(cache.get(&key), None);
+    }
+
+    #[test]
+    fn runner_solve_unknown_solver() {
+        let options = RunOptions {
+            part: None,
+            solver: Some(String::from("fast")),
+            ..RunOptions::default()
+        };
+
+        let result = solve(6, &mut "Time: 7\nDistance: 9".as_bytes(), &options);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,394 @@
+use std::{
+    fmt::{self, Display},
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::{answer::Answer, client::Client, error::ChallengeError};
+
+pub const HISTORY_FILE: &str = "submissions.txt";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    RateLimited,
+    // The part was already solved, or part two is not unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+impl Verdict {
+    const KEYS: [(Verdict, &'static str); 7] = [
+        (Verdict::Correct, "correct"),
+        (Verdict::Incorrect, "incorrect"),
+        (Verdict::TooHigh, "too-high"),
+        (Verdict::TooLow, "too-low"),
+        (Verdict::RateLimited, "rate-limited"),
+        (Verdict::WrongLevel, "wrong-level"),
+        (Verdict::Unknown, "unknown"),
+    ];
+
+    pub fn from(key: &str) -> Option<Self> {
+        Verdict::KEYS
+            .iter()
+            .find(|(_, name)| *name == key)
+            .map(|(verdict, _)| *verdict)
+    }
+
+    // How the verdict is written in the history file.
+    pub fn key(&self) -> &'static str {
+        Verdict::KEYS
+            .iter()
+            .find(|(verdict, _)| verdict == self)
+            .map(|(_, key)| *key)
+            .unwrap_or("unknown")
+    }
+
+    // Whether the server judged the answer itself. Only these block the same
+    // answer from being submitted again.
+    pub fn is_judged(&self) -> bool {
+        matches!(
+            self,
+            Verdict::Correct | Verdict::Incorrect | Verdict::TooHigh | Verdict::TooLow
+        )
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.key().replace('-', " "))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub verdict: Verdict,
+    pub message: String,
+}
+
+// The outcome is in the page's `<article>`, e.g. "That's not the right
+// answer; your answer is too high."
+pub fn parse_response(html: &str) -> Response {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map(|(article, _)| article)
+        .unwrap_or(html);
+    let message = strip_tags(article);
+
+    let verdict = if message.contains("That's the right answer") {
+        Verdict::Correct
+    } else if message.contains("You gave an answer too recently") {
+        Verdict::RateLimited
+    } else if message.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else if message.contains("That's not the right answer") {
+        if message.contains("too high") {
+            Verdict::TooHigh
+        } else if message.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Incorrect
+        }
+    } else {
+        Verdict::Unknown
+    };
+
+    Response { verdict, message }
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Submission {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub verdict: Verdict,
+    pub answer: Answer,
+}
+
+// Every submission, one per line as `<year> <day> <part> <verdict> <answer>`.
+#[derive(Debug)]
+pub struct History {
+    path: PathBuf,
+    submissions: Vec<Submission>,
+}
+
+impl History {
+    // A missing file is an empty history.
+    pub fn load(path: &Path) -> Result<Self, ChallengeError> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => {
+                return Err(ChallengeError::io(&format!(
+                    "Could not read {}: {}",
+                    path.display(),
+                    e
+                )))
+            }
+        };
+
+        let submissions = content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| parse_submission(line).map_err(|e| e.at_line(index + 1)))
+            .collect::<Result<Vec<Submission>, ChallengeError>>()?;
+
+        Ok(History {
+            path: path.to_path_buf(),
+            submissions,
+        })
+    }
+
+    pub fn submissions(&self) -> &[Submission] {
+        &self.submissions
+    }
+
+    // The judged submission of this exact answer, if there was one.
+    pub fn previous(&self, year: u16, day: u8, part: u8, answer: &Answer) -> Option<&Submission> {
+        self.judged(year, day, part)
+            .find(|submission| submission.answer == *answer)
+    }
+
+    pub fn solved(&self, year: u16, day: u8, part: u8) -> Option<&Submission> {
+        self.judged(year, day, part)
+            .find(|submission| submission.verdict == Verdict::Correct)
+    }
+
+    fn judged(&self, year: u16, day: u8, part: u8) -> impl Iterator<Item = &Submission> {
+        self.submissions.iter().filter(move |submission| {
+            submission.year == year
+                && submission.day == day
+                && submission.part == part
+                && submission.verdict.is_judged()
+        })
+    }
+
+    pub fn record(&mut self, submission: Submission) -> Result<(), ChallengeError> {
+        let error = |e: io::Error| {
+            ChallengeError::io(&format!("Could not write {}: {}", self.path.display(), e))
+        };
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(error)?;
+        }
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(error)?;
+        writeln!(
+            file,
+            "{} {} {} {} {}",
+            submission.year,
+            submission.day,
+            submission.part,
+            submission.verdict.key(),
+            submission.answer
+        )
+        .map_err(error)?;
+
+        self.submissions.push(submission);
+        Ok(())
+    }
+}
+
+fn parse_submission(line: &str) -> Result<Submission, ChallengeError> {
+    let mut fields = line.splitn(5, ' ');
+    let mut field = |name: &str| {
+        fields
+            .next()
+            .filter(|field| !field.is_empty())
+            .ok_or_else(|| ChallengeError::parse(line, 1, &format!("Missing {}", name)))
+    };
+    let year = number(field("year")?, "year")?;
+    let day = number(field("day")?, "day")?;
+    let part = number(field("part")?, "part")?;
+    let verdict = field("verdict")?;
+    let verdict = Verdict::from(verdict)
+        .ok_or_else(|| ChallengeError::parse(verdict, 1, "Unknown verdict"))?;
+
+    Ok(Submission {
+        year,
+        day,
+        part,
+        verdict,
+        answer: Answer::parse(field("answer")?),
+    })
+}
+
+fn number<T: FromStr>(text: &str, name: &str) -> Result<T, ChallengeError> {
+    text.parse()
+        .map_err(|_| ChallengeError::parse(text, 1, &format!("Could not parse {}", name)))
+}
+
+// Posts an answer unless the history shows it was already judged, then
+// records the outcome.
+pub fn submit(
+    client: &Client,
+    history: &mut History,
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: &Answer,
+) -> Result<Response, ChallengeError> {
+    if !answer.is_implemented() {
+        return Err(
+            ChallengeError::solve(&format!("Part {} is not implemented", part)).with_day(day),
+        );
+    }
+    if let Some(previous) = history.solved(year, day, part) {
+        return Err(ChallengeError::solve(&format!(
+            "Part {} was already solved with {}",
+            part, previous.answer
+        ))
+        .with_day(day));
+    }
+    if let Some(previous) = history.previous(year, day, part, answer) {
+        return Err(ChallengeError::solve(&format!(
+            "{} was already submitted for part {} and was {}",
+            answer, part, previous.verdict
+        ))
+        .with_day(day));
+    }
+
+    let html = client
+        .post_form(
+            &format!("/{}/day/{}/answer", year, day),
+            &[
+                ("level", &part.to_string()),
+                ("answer", &answer.to_string()),
+            ],
+        )
+        .map_err(|e| e.with_day(day))?;
+    let response = parse_response(&html);
+    history.record(Submission {
+        year,
+        day,
+        part,
+        verdict: response.verdict,
+        answer: answer.clone(),
+    })?;
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        client::test::{client, serve},
+        utils::temp_dir::TempDir,
+    };
+
+    const TOO_HIGH: &str = "<html><body><main><article><p>That's not the right answer; \
+        your answer is too high. If you're stuck, make sure you're using the full input data.\
+        </p></article></main></body></html>";
+    const CORRECT: &str = "<main><article><p>That's the right answer! You are \
+        <span class=\"day-success\">one gold star</span> closer.</p></article></main>";
+
+    #[test]
+    fn submit_parse_response_verdicts() {
+        assert_eq!(parse_response(TOO_HIGH).verdict, Verdict::TooHigh);
+        assert_eq!(
+            parse_response(CORRECT),
+            Response {
+                verdict: Verdict::Correct,
+                message: String::from("That's the right answer! You are one gold star closer."),
+            }
+        );
+        assert_eq!(
+            parse_response(
+                "<article><p>That's not the right answer; your answer is too low.</p></article>"
+            )
+            .verdict,
+            Verdict::TooLow
+        );
+        assert_eq!(
+            parse_response("<article><p>That's not the right answer.</p></article>").verdict,
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            parse_response("<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 34s left to wait.</p></article>").verdict,
+            Verdict::RateLimited
+        );
+        assert_eq!(
+            parse_response("<article><p>You don't seem to be solving the right level. Did you already complete it?</p></article>").verdict,
+            Verdict::WrongLevel
+        );
+        assert_eq!(parse_response("<html></html>").verdict, Verdict::Unknown);
+    }
+
+    #[test]
+    fn submit_history_round_trips() {
+        let dir = TempDir::new("submit-history");
+        let path = dir.join(HISTORY_FILE);
+        let submission = Submission {
+            year: 2023,
+            day: 6,
+            part: 1,
+            verdict: Verdict::TooLow,
+            answer: Answer::from(288),
+        };
+
+        let mut history = History::load(&path).unwrap();
+        history.record(submission.clone()).unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "2023 6 1 too-low 288\n");
+        assert_eq!(History::load(&path).unwrap().submissions(), &[submission]);
+        fs::write(&path, "2023 6 1 maybe 288\n").unwrap();
+        assert!(History::load(&path).is_err());
+    }
+
+    #[test]
+    fn submit_posts_answer_and_never_repeats_wrong_answer() {
+        let (base_url, server) = serve(vec![(200, TOO_HIGH), (200, CORRECT)]);
+        let dir = TempDir::new("submit-posts");
+        let client = client(&base_url, &dir);
+        let mut history = History::load(&dir.join(HISTORY_FILE)).unwrap();
+
+        let response = submit(&client, &mut history, 2023, 6, 1, &Answer::from(300)).unwrap();
+        assert_eq!(response.verdict, Verdict::TooHigh);
+
+        // The same wrong answer is refused without a request.
+        let error = submit(&client, &mut history, 2023, 6, 1, &Answer::from(300)).err();
+        assert_eq!(
+            error,
+            Some(
+                ChallengeError::solve("300 was already submitted for part 1 and was too high")
+                    .with_day(6)
+            )
+        );
+
+        let response = submit(&client, &mut history, 2023, 6, 1, &Answer::from(288)).unwrap();
+        assert_eq!(response.verdict, Verdict::Correct);
+        assert!(submit(&client, &mut history, 2023, 6, 1, &Answer::from(289)).is_err());
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].starts_with("POST /2023/day/6/answer "));
+        assert!(requests[0].ends_with("level=1&answer=300"));
+        assert!(requests[1].ends_with("level=1&answer=288"));
+    }
+}
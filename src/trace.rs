@@ -0,0 +1,170 @@
+use std::{
+    cell::RefCell,
+    fmt::{self, Display},
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TraceLevel {
+    #[default]
+    Off,
+    Progress,
+    Debug,
+    Trace,
+}
+
+impl TraceLevel {
+    pub const NAMES: [&'static str; 4] = ["off", "progress", "debug", "trace"];
+
+    pub fn from(name: &str) -> Option<Self> {
+        match name {
+            "off" => Some(TraceLevel::Off),
+            "progress" => Some(TraceLevel::Progress),
+            "debug" => Some(TraceLevel::Debug),
+            "trace" => Some(TraceLevel::Trace),
+            _ => None,
+        }
+    }
+}
+
+impl Display for TraceLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TraceLevel::Off => "off",
+            TraceLevel::Progress => "progress",
+            TraceLevel::Debug => "debug",
+            TraceLevel::Trace => "trace",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// Where solvers send diagnostic output, so it never mixes with answers on
+// stdout. Messages above the configured level are dropped without being
+// formatted.
+pub struct Trace {
+    level: TraceLevel,
+    sink: RefCell<Box<dyn Write>>,
+}
+
+impl Default for Trace {
+    fn default() -> Self {
+        Trace::new(TraceLevel::Off, Box::new(io::sink()))
+    }
+}
+
+impl fmt::Debug for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Trace").field("level", &self.level).finish()
+    }
+}
+
+impl Trace {
+    pub fn new(level: TraceLevel, sink: Box<dyn Write>) -> Self {
+        Trace {
+            level,
+            sink: RefCell::new(sink),
+        }
+    }
+
+    pub fn stderr(level: TraceLevel) -> Self {
+        Trace::new(level, Box::new(io::stderr()))
+    }
+
+    pub fn file(level: TraceLevel, path: &Path) -> io::Result<Self> {
+        Ok(Trace::new(level, Box::new(File::create(path)?)))
+    }
+
+    pub fn level(&self) -> TraceLevel {
+        self.level
+    }
+
+    pub fn enabled(&self, level: TraceLevel) -> bool {
+        level != TraceLevel::Off && level <= self.level
+    }
+
+    pub fn log(&self, level: TraceLevel, message: fmt::Arguments) {
+        if !self.enabled(level) {
+            return;
+        }
+
+        // Tracing must never fail a solve, so write errors are ignored.
+        let mut sink = self.sink.borrow_mut();
+        let _ = writeln!(sink, "[{}] {}", level, message);
+        let _ = sink.flush();
+    }
+
+    pub fn progress(&self, message: fmt::Arguments) {
+        self.log(TraceLevel::Progress, message);
+    }
+
+    pub fn debug(&self, message: fmt::Arguments) {
+        self.log(TraceLevel::Debug, message);
+    }
+
+    pub fn trace(&self, message: fmt::Arguments) {
+        self.log(TraceLevel::Trace, message);
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::rc::Rc;
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    pub(crate) struct Captured(Rc<RefCell<Vec<u8>>>);
+
+    impl Captured {
+        pub(crate) fn contents(&self) -> String {
+            String::from_utf8(self.0.borrow().clone()).unwrap()
+        }
+    }
+
+    impl Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    pub(crate) fn captured(level: TraceLevel) -> (Trace, Captured) {
+        let captured = Captured::default();
+        (Trace::new(level, Box::new(captured.clone())), captured)
+    }
+
+    #[test]
+    fn trace_filters_by_level() {
+        let (trace, captured) = captured(TraceLevel::Debug);
+        trace.progress(format_args!("Travelled {} steps.", 1000000));
+        trace.debug(format_args!("debug"));
+        trace.trace(format_args!("too detailed"));
+
+        assert_eq!(
+            captured.contents(),
+            "[progress] Travelled 1000000 steps.\n[debug] debug\n"
+        );
+    }
+
+    #[test]
+    fn trace_is_silent_by_default() {
+        let trace = Trace::default();
+
+        assert!(!trace.enabled(TraceLevel::Progress));
+        assert!(!trace.enabled(TraceLevel::Off));
+    }
+
+    #[test]
+    fn trace_level_names() {
+        for name in TraceLevel::NAMES {
+            assert_eq!(TraceLevel::from(name).unwrap().to_string(), name);
+        }
+        assert_eq!(TraceLevel::from("verbose"), None);
+    }
+}
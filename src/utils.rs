@@ -1,12 +1,219 @@
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
+use std::io::BufRead;
+
+use crate::error::ChallengeError;
+
+pub use self::{
+    adjacent::{Adjacent, Neighborhood},
+    grid::Grid,
 };
 
-pub fn to_lines_vec(file: &File) -> Vec<String> {
-    let reader = BufReader::new(file);
+pub mod adjacent;
+pub mod grid;
+#[cfg(test)]
+pub mod temp_dir;
+
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+pub fn to_lines_vec(reader: &mut dyn BufRead) -> Result<Vec<String>, ChallengeError> {
     reader
         .lines()
-        .map(|line| line.expect("Could not read line"))
-        .collect::<Vec<String>>()
+        .enumerate()
+        .map(|(i, line)| {
+            line.map_err(|e| ChallengeError::io(&format!("Could not read line {}: {}", i + 1, e)))
+        })
+        .collect()
+}
+
+// Reads the whole input without a leading byte order mark, carriage returns,
+// trailing whitespace or trailing blank lines, so parsers that slice fixed
+// offsets or split on single spaces see the same text on every platform.
+pub fn read_normalized(reader: &mut dyn BufRead) -> Result<String, ChallengeError> {
+    Ok(normalize(&read_to_string(reader)?))
+}
+
+// Like `read_normalized`, but rejects anything that would need normalizing,
+// pointing at the first offending line and column.
+pub fn read_strict(reader: &mut dyn BufRead) -> Result<String, ChallengeError> {
+    let input = read_to_string(reader)?;
+    match find_anomaly(&input) {
+        Some(error) => Err(error),
+        None => Ok(input),
+    }
+}
+
+fn read_to_string(reader: &mut dyn BufRead) -> Result<String, ChallengeError> {
+    let mut input = String::new();
+    reader
+        .read_to_string(&mut input)
+        .map_err(|e| ChallengeError::io(&format!("Could not read input: {}", e)))?;
+    Ok(input)
+}
+
+pub fn normalize(input: &str) -> String {
+    let input = input.strip_prefix(BYTE_ORDER_MARK).unwrap_or(input);
+    let mut lines: Vec<&str> = input.split('\n').map(str::trim_end).collect();
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+
+    if lines.is_empty() {
+        return String::new();
+    }
+    lines.join("\n") + "\n"
+}
+
+pub fn find_anomaly(input: &str) -> Option<ChallengeError> {
+    if input.starts_with(BYTE_ORDER_MARK) {
+        return Some(
+            ChallengeError::parse(
+                &BYTE_ORDER_MARK.to_string(),
+                1,
+                "Input starts with a byte order mark",
+            )
+            .at_line(1),
+        );
+    }
+
+    let lines: Vec<&str> = input.split('\n').collect();
+    let last_content = lines.iter().rposition(|line| !line.trim().is_empty());
+    for (i, line) in lines.iter().enumerate() {
+        let content = line.trim_end();
+        let column = content.chars().count() + 1;
+        if line.ends_with('\r') {
+            return Some(
+                ChallengeError::parse("\r", line.chars().count(), "Line ends with CRLF")
+                    .at_line(i + 1),
+            );
+        }
+        if content.len() < line.len() {
+            return Some(
+                ChallengeError::parse(&line[content.len()..], column, "Trailing whitespace")
+                    .at_line(i + 1),
+            );
+        }
+        // The empty string after the final newline is not a blank line.
+        let is_trailing = last_content.is_none_or(|last_content| i > last_content);
+        if is_trailing && i + 1 < lines.len() {
+            return Some(
+                ChallengeError::parse("", 1, "Input ends with blank lines").at_line(i + 1),
+            );
+        }
+    }
+
+    None
+}
+
+// Splits `text` on `separator`, pairing each piece with its byte offset in
+// `text` so parse errors can report the column it starts at.
+pub fn split_offsets(text: &str, separator: char) -> impl Iterator<Item = (usize, &str)> + '_ {
+    let mut offset = 0;
+    text.split(separator).map(move |piece| {
+        let start = offset;
+        offset += piece.len() + separator.len_utf8();
+        (start, piece)
+    })
+}
+
+// Parse errors point at the line the TOML error starts on.
+pub fn parse_toml_table(content: &str) -> Result<toml::Table, ChallengeError> {
+    content.parse::<toml::Table>().map_err(|e| {
+        let line = e
+            .span()
+            .and_then(|span| content.get(..span.start))
+            .map_or(1, |before| before.matches('\n').count() + 1);
+        ChallengeError::parse("", 1, e.message()).at_line(line)
+    })
+}
+
+// 64-bit FNV-1a, which is stable across platforms and Rust versions unlike
+// the standard library's hasher.
+pub fn input_hash(input: &[u8]) -> String {
+    let hash = input.iter().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn utils_to_lines_vec_from_str() {
+        let lines = to_lines_vec(&mut "first\nsecond\r\n\nlast".as_bytes()).unwrap();
+        assert_eq!(lines, vec!["first", "second", "", "last"]);
+    }
+
+    #[test]
+    fn utils_normalize_strips_anomalies() {
+        let input = "\u{feff}LR  \r\n\r\nAAA = (BBB, CCC)\t\r\n\r\n\n";
+        assert_eq!(normalize(input), "LR\n\nAAA = (BBB, CCC)\n");
+        assert_eq!(normalize("\n\n"), "");
+        assert_eq!(normalize("a\nb"), "a\nb\n");
+    }
+
+    #[test]
+    fn utils_read_normalized() {
+        let input = read_normalized(&mut "\u{feff}1abc2\r\n".as_bytes()).unwrap();
+        assert_eq!(input, "1abc2\n");
+    }
+
+    #[test]
+    fn utils_find_anomaly_locations() {
+        assert_eq!(find_anomaly("LR\n\nAAA = (BBB, CCC)\n"), None);
+        assert_eq!(find_anomaly("LR"), None);
+        assert_eq!(find_anomaly(""), None);
+        assert_eq!(
+            find_anomaly("\u{feff}LR\n"),
+            Some(
+                ChallengeError::parse("\u{feff}", 1, "Input starts with a byte order mark")
+                    .at_line(1)
+            )
+        );
+        assert_eq!(
+            find_anomaly("LR\n\nAAA\r\n"),
+            Some(ChallengeError::parse("\r", 4, "Line ends with CRLF").at_line(3))
+        );
+        assert_eq!(
+            find_anomaly("LR\nAAA = (BBB, CCC) \t\n"),
+            Some(ChallengeError::parse(" \t", 17, "Trailing whitespace").at_line(2))
+        );
+        assert_eq!(
+            find_anomaly("LR\n\nAAA\n\n"),
+            Some(ChallengeError::parse("", 1, "Input ends with blank lines").at_line(4))
+        );
+    }
+
+    #[test]
+    fn utils_read_strict() {
+        assert_eq!(read_strict(&mut "1abc2\n".as_bytes()).unwrap(), "1abc2\n");
+        assert!(read_strict(&mut "1abc2\r\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn utils_split_offsets() {
+        let pieces: Vec<(usize, &str)> = split_offsets("seeds: 79  14", ' ').collect();
+        assert_eq!(pieces, vec![(0, "seeds:"), (7, "79"), (10, ""), (11, "14")]);
+        assert_eq!(split_offsets("", ';').collect::<Vec<_>>(), vec![(0, "")]);
+    }
+
+    #[test]
+    fn utils_parse_toml_table_reports_line() {
+        let line = |content: &str| match parse_toml_table(content).unwrap_err() {
+            ChallengeError::Parse { line, .. } => line,
+            error => panic!("Expected a parse error, got {:?}", error),
+        };
+
+        assert_eq!(line("= 1"), Some(1));
+        assert_eq!(line("a = 1\n= 2"), Some(2));
+        assert_eq!(line("a = 1\nb = 2\nc = \n"), Some(3));
+        assert!(parse_toml_table("a = 1").is_ok());
+    }
+
+    #[test]
+    fn utils_input_hash() {
+        assert_eq!(input_hash(b""), "cbf29ce484222325");
+        assert_eq!(input_hash(b"a"), "af63dc4c8601ec8c");
+        assert_ne!(input_hash(b"1\n2"), input_hash(b"2\n1"));
+    }
 }
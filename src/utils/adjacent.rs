@@ -0,0 +1,314 @@
+use std::borrow::Cow;
+
+use super::grid::Grid;
+
+// Offsets in reading order, so neighbours come out row by row.
+pub const VON_NEUMANN: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+pub const MOORE: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+// Which cells count as adjacent. Without wrapping, offsets that fall outside
+// the grid are skipped; with it they continue on the opposite edge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Neighborhood {
+    offsets: Cow<'static, [(isize, isize)]>,
+    wrap: bool,
+}
+
+impl Neighborhood {
+    // Cells within `radius` steps up, down, left or right.
+    pub fn von_neumann(radius: usize) -> Self {
+        match radius {
+            1 => Neighborhood::from_static(&VON_NEUMANN),
+            _ => Neighborhood::within(radius, |row, col| {
+                row.unsigned_abs() + col.unsigned_abs() <= radius
+            }),
+        }
+    }
+
+    // Cells within `radius` steps in any direction, diagonals included.
+    pub fn moore(radius: usize) -> Self {
+        match radius {
+            1 => Neighborhood::from_static(&MOORE),
+            _ => Neighborhood::within(radius, |_, _| true),
+        }
+    }
+
+    // Offsets are visited in the order given.
+    pub fn custom(offsets: Vec<(isize, isize)>) -> Self {
+        Neighborhood {
+            offsets: Cow::Owned(offsets),
+            wrap: false,
+        }
+    }
+
+    pub fn with_wrap(mut self) -> Self {
+        self.wrap = true;
+        self
+    }
+
+    pub fn offsets(&self) -> &[(isize, isize)] {
+        &self.offsets
+    }
+
+    pub fn wraps(&self) -> bool {
+        self.wrap
+    }
+
+    fn from_static(offsets: &'static [(isize, isize)]) -> Self {
+        Neighborhood {
+            offsets: Cow::Borrowed(offsets),
+            wrap: false,
+        }
+    }
+
+    // Every offset in the square of `radius` accepted by `include`, except
+    // the cell itself.
+    fn within(radius: usize, include: impl Fn(isize, isize) -> bool) -> Self {
+        let radius = radius as isize;
+        let offsets = (-radius..=radius)
+            .flat_map(|row| (-radius..=radius).map(move |col| (row, col)))
+            .filter(|&(row, col)| (row, col) != (0, 0) && include(row, col))
+            .collect();
+
+        Neighborhood::custom(offsets)
+    }
+}
+
+// The cells around a position. A wrapping neighbourhood can reach the same
+// cell twice, or the position itself, on a small grid; those are skipped.
+pub struct Adjacent<'a, T> {
+    grid: &'a Grid<T>,
+    cell: (usize, usize),
+    neighborhood: Neighborhood,
+    offset_index: usize,
+    seen: Vec<(usize, usize)>,
+}
+
+impl<'a, T> Adjacent<'a, T> {
+    pub fn new(grid: &'a Grid<T>, cell: (usize, usize), neighborhood: Neighborhood) -> Self {
+        Adjacent {
+            grid,
+            cell,
+            neighborhood,
+            offset_index: 0,
+            seen: Vec::new(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Adjacent<'a, T> {
+    type Item = ((usize, usize), &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(offset) = self.neighborhood.offsets.get(self.offset_index) {
+            self.offset_index += 1;
+            if !self.neighborhood.wrap {
+                if let Some(position) = self.grid.offset(self.cell, *offset) {
+                    return Some((position, &self.grid[position]));
+                }
+                continue;
+            }
+
+            let position = self.grid.wrapping_offset(self.cell, *offset)?;
+            if position != self.cell && !self.seen.contains(&position) {
+                self.seen.push(position);
+                return Some((position, &self.grid[position]));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_grid() -> Grid<u8> {
+        Grid::new(vec![
+            vec![1, 2, 3, 4, 5],
+            vec![1, 2, 3, 4, 5],
+            vec![1, 2, 3, 4, 5],
+            vec![1, 2, 3, 4, 5],
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn adjacent_all_values() {
+        let grid = get_grid();
+
+        let adjacent = Adjacent::new(&grid, (2, 2), Neighborhood::moore(1));
+        let collected_iter: Vec<u8> = adjacent.map(|(_, value)| *value).collect();
+
+        let expected = vec![2, 3, 4, 2, 4, 2, 3, 4];
+        assert_eq!(collected_iter, expected);
+    }
+
+    #[test]
+    fn adjacent_edge() {
+        let grid = get_grid();
+        let adjacent = Adjacent::new(&grid, (0, 1), Neighborhood::moore(1));
+        let collected_iter: Vec<u8> = adjacent.map(|(_, value)| *value).collect();
+
+        let expected = vec![1, 3, 1, 2, 3];
+        assert_eq!(collected_iter, expected);
+    }
+
+    #[test]
+    fn adjacent_bottom_right_corner() {
+        let grid = get_grid();
+        let positions: Vec<(usize, usize)> = Adjacent::new(&grid, (3, 4), Neighborhood::moore(1))
+            .map(|(position, _)| position)
+            .collect();
+
+        assert_eq!(positions, vec![(2, 3), (2, 4), (3, 3)]);
+    }
+
+    fn positions(position: (usize, usize), neighborhood: Neighborhood) -> Vec<(usize, usize)> {
+        let grid = get_grid();
+        Adjacent::new(&grid, position, neighborhood)
+            .map(|(position, _)| position)
+            .collect()
+    }
+
+    #[test]
+    fn adjacent_von_neumann_corner_and_edge() {
+        assert_eq!(
+            positions((0, 0), Neighborhood::von_neumann(1)),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            positions((0, 2), Neighborhood::von_neumann(1)),
+            vec![(0, 1), (0, 3), (1, 2)]
+        );
+        assert_eq!(
+            positions((2, 4), Neighborhood::von_neumann(1)),
+            vec![(1, 4), (2, 3), (3, 4)]
+        );
+    }
+
+    #[test]
+    fn adjacent_moore_top_left_corner() {
+        assert_eq!(
+            positions((0, 0), Neighborhood::moore(1)),
+            vec![(0, 1), (1, 0), (1, 1)]
+        );
+    }
+
+    #[test]
+    fn adjacent_radius() {
+        assert_eq!(
+            positions((0, 0), Neighborhood::moore(2)),
+            vec![
+                (0, 1),
+                (0, 2),
+                (1, 0),
+                (1, 1),
+                (1, 2),
+                (2, 0),
+                (2, 1),
+                (2, 2)
+            ]
+        );
+        assert_eq!(
+            positions((0, 0), Neighborhood::von_neumann(2)),
+            vec![(0, 1), (0, 2), (1, 0), (1, 1), (2, 0)]
+        );
+        assert_eq!(
+            positions((3, 2), Neighborhood::von_neumann(2)),
+            vec![
+                (1, 2),
+                (2, 1),
+                (2, 2),
+                (2, 3),
+                (3, 0),
+                (3, 1),
+                (3, 3),
+                (3, 4)
+            ]
+        );
+        assert_eq!(Neighborhood::moore(2).offsets().len(), 24);
+        assert!(positions((1, 1), Neighborhood::moore(0)).is_empty());
+    }
+
+    #[test]
+    fn adjacent_custom_offsets() {
+        let knight = || {
+            Neighborhood::custom(vec![
+                (-2, -1),
+                (-2, 1),
+                (-1, -2),
+                (-1, 2),
+                (1, -2),
+                (1, 2),
+                (2, -1),
+                (2, 1),
+            ])
+        };
+
+        assert_eq!(positions((0, 0), knight()), vec![(1, 2), (2, 1)]);
+        assert_eq!(positions((3, 4), knight()), vec![(1, 3), (2, 2)]);
+        // Rows two apart meet again on a four row grid.
+        assert_eq!(
+            positions((0, 2), knight().with_wrap()),
+            vec![(2, 1), (2, 3), (3, 0), (3, 4), (1, 0), (1, 4)]
+        );
+    }
+
+    #[test]
+    fn adjacent_wrap_corners_and_edges() {
+        assert_eq!(
+            positions((0, 0), Neighborhood::von_neumann(1).with_wrap()),
+            vec![(3, 0), (0, 4), (0, 1), (1, 0)]
+        );
+        assert_eq!(
+            positions((3, 4), Neighborhood::moore(1).with_wrap()),
+            vec![
+                (2, 3),
+                (2, 4),
+                (2, 0),
+                (3, 3),
+                (3, 0),
+                (0, 3),
+                (0, 4),
+                (0, 0)
+            ]
+        );
+        assert_eq!(
+            positions((0, 2), Neighborhood::moore(1).with_wrap()),
+            vec![
+                (3, 1),
+                (3, 2),
+                (3, 3),
+                (0, 1),
+                (0, 3),
+                (1, 1),
+                (1, 2),
+                (1, 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn adjacent_wrap_skips_repeats_and_self() {
+        // Four rows, so offsets of -2 and 2 reach the same row.
+        let wrapped = positions((0, 0), Neighborhood::moore(2).with_wrap());
+
+        assert_eq!(wrapped.len(), 19);
+        assert!(!wrapped.contains(&(0, 0)));
+        let grid = Grid::new(vec![vec![1]]).unwrap();
+        assert_eq!(
+            Adjacent::new(&grid, (0, 0), Neighborhood::moore(1).with_wrap()).count(),
+            0
+        );
+    }
+}
@@ -0,0 +1,352 @@
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
+
+use super::adjacent::{Adjacent, Neighborhood};
+use crate::error::ChallengeError;
+
+// A rectangular grid stored row by row. Positions are `(row, column)`,
+// counted from the top left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+// Empty, without requiring `T: Default`.
+impl<T> Default for Grid<T> {
+    fn default() -> Self {
+        Grid {
+            cells: Vec::new(),
+            width: 0,
+            height: 0,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn new(rows: Vec<Vec<T>>) -> Result<Self, ChallengeError> {
+        let width = rows.first().map(Vec::len).unwrap_or(0);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (index, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(ChallengeError::parse(
+                    "",
+                    row.len().min(width) + 1,
+                    &format!("Expected {} columns, found {}", width, row.len()),
+                )
+                .at_line(index + 1));
+            }
+            cells.extend(row);
+        }
+
+        Ok(Grid {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    // Builds each cell from its character and position. Rows of different
+    // lengths are rejected.
+    pub fn parse(
+        lines: &[String],
+        mut parse_cell: impl FnMut(char, (usize, usize)) -> Result<T, ChallengeError>,
+    ) -> Result<Self, ChallengeError> {
+        let rows = lines
+            .iter()
+            .enumerate()
+            .map(|(row, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(col, c)| parse_cell(c, (row, col)))
+                    .collect::<Result<Vec<T>, ChallengeError>>()
+            })
+            .collect::<Result<Vec<Vec<T>>, ChallengeError>>()?;
+
+        Grid::new(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (row, col): (usize, usize)) -> Option<&T> {
+        if row >= self.height || col >= self.width {
+            return None;
+        }
+        self.cells.get(row * self.width + col)
+    }
+
+    pub fn get_mut(&mut self, (row, col): (usize, usize)) -> Option<&mut T> {
+        if row >= self.height || col >= self.width {
+            return None;
+        }
+        self.cells.get_mut(row * self.width + col)
+    }
+
+    // The position `offset` away from `position`, if it is inside the grid.
+    pub fn offset(
+        &self,
+        (row, col): (usize, usize),
+        (row_offset, col_offset): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let row = row.checked_add_signed(row_offset)?;
+        let col = col.checked_add_signed(col_offset)?;
+        (row < self.height && col < self.width).then_some((row, col))
+    }
+
+    // Like `offset`, but leaving one edge enters at the opposite one. `None`
+    // only for an empty grid.
+    pub fn wrapping_offset(
+        &self,
+        (row, col): (usize, usize),
+        (row_offset, col_offset): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        if self.width == 0 || self.height == 0 {
+            return None;
+        }
+        let wrap = |index: usize, offset: isize, size: usize| {
+            (index as isize + offset).rem_euclid(size as isize) as usize
+        };
+        Some((
+            wrap(row, row_offset, self.height),
+            wrap(col, col_offset, self.width),
+        ))
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        (row < self.height).then(|| &self.cells[row * self.width..(row + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|row| &self.cells[row * self.width..(row + 1) * self.width])
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        let cells = if col < self.width {
+            &self.cells[col..]
+        } else {
+            &[]
+        };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    // Every cell with its position, in reading order.
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width.max(1);
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(index, cell)| ((index / width, index % width), cell))
+    }
+
+    // The up to four cells sharing an edge with `position`.
+    pub fn neighbors4(&self, position: (usize, usize)) -> Adjacent<'_, T> {
+        self.neighbors(position, Neighborhood::von_neumann(1))
+    }
+
+    // The up to eight cells sharing an edge or a corner with `position`.
+    pub fn neighbors8(&self, position: (usize, usize)) -> Adjacent<'_, T> {
+        self.neighbors(position, Neighborhood::moore(1))
+    }
+
+    pub fn neighbors(
+        &self,
+        position: (usize, usize),
+        neighborhood: Neighborhood,
+    ) -> Adjacent<'_, T> {
+        Adjacent::new(self, position, neighborhood)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    // Builds a `height` by `width` grid from the cell at each new position.
+    fn rearranged(
+        &self,
+        width: usize,
+        height: usize,
+        from: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Self {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(|(row, col)| self[from(row, col)].clone())
+            .collect();
+
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    pub fn transpose(&self) -> Self {
+        self.rearranged(self.height, self.width, |row, col| (col, row))
+    }
+
+    pub fn rotate_clockwise(&self) -> Self {
+        let height = self.height;
+        self.rearranged(self.height, self.width, |row, col| (height - 1 - col, row))
+    }
+
+    pub fn rotate_counter_clockwise(&self) -> Self {
+        let width = self.width;
+        self.rearranged(self.height, self.width, |row, col| (col, width - 1 - row))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: (usize, usize)) -> &Self::Output {
+        self.get(position)
+            .unwrap_or_else(|| panic!("Position {:?} is outside the grid.", position))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, position: (usize, usize)) -> &mut Self::Output {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("Position {:?} is outside the grid.", position))
+    }
+}
+
+// Prints one line per row, e.g. to compare a parsed grid against the input.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, row) in self.rows().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn get_grid() -> Grid<char> {
+        let lines = vec![String::from("abc"), String::from("def")];
+        Grid::parse(&lines, |c, _| Ok(c)).unwrap()
+    }
+
+    #[test]
+    fn grid_parse_dimensions_and_get() {
+        let grid = get_grid();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get((1, 2)), Some(&'f'));
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid[(0, 1)], 'b');
+    }
+
+    #[test]
+    fn grid_parse_rejects_ragged_rows() {
+        let lines = vec![String::from("abc"), String::from("de")];
+
+        assert_eq!(
+            Grid::parse(&lines, |c, _| Ok(c)),
+            Err(ChallengeError::parse("", 3, "Expected 3 columns, found 2").at_line(2))
+        );
+    }
+
+    #[test]
+    fn grid_parse_cell_errors() {
+        let lines = vec![String::from("12"), String::from("3x")];
+        let error = Grid::parse(&lines, |c, (row, col)| {
+            c.to_digit(10).ok_or_else(|| {
+                ChallengeError::parse(&c.to_string(), col + 1, "Expected a digit").at_line(row + 1)
+            })
+        });
+
+        assert_eq!(
+            error,
+            Err(ChallengeError::parse("x", 2, "Expected a digit").at_line(2))
+        );
+    }
+
+    #[test]
+    fn grid_rows_and_columns() {
+        let grid = get_grid();
+
+        assert_eq!(grid.row(1), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.rows().count(), 2);
+        let columns: Vec<String> = grid.columns().map(|column| column.collect()).collect();
+        assert_eq!(columns, vec!["ad", "be", "cf"]);
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(grid.cells().nth(4), Some(((1, 1), &'e')));
+    }
+
+    #[test]
+    fn grid_neighbors() {
+        let grid = get_grid();
+
+        let four: Vec<char> = grid.neighbors4((0, 1)).map(|(_, c)| *c).collect();
+        assert_eq!(four, vec!['a', 'c', 'e']);
+        let eight: Vec<((usize, usize), char)> = grid
+            .neighbors8((1, 2))
+            .map(|(position, c)| (position, *c))
+            .collect();
+        assert_eq!(eight, vec![((0, 1), 'b'), ((0, 2), 'c'), ((1, 1), 'e')]);
+    }
+
+    #[test]
+    fn grid_offset() {
+        let grid = get_grid();
+
+        assert_eq!(grid.offset((0, 0), (1, 2)), Some((1, 2)));
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((1, 2), (0, 1)), None);
+        assert_eq!(grid.wrapping_offset((0, 0), (-1, -1)), Some((1, 2)));
+        assert_eq!(grid.wrapping_offset((1, 2), (3, 4)), Some((0, 0)));
+        assert_eq!(
+            Grid::<char>::default().wrapping_offset((0, 0), (1, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn grid_transpose_and_rotate() {
+        let grid = get_grid();
+
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counter_clockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_clockwise().rotate_counter_clockwise(), grid);
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+
+    #[test]
+    fn grid_map_and_display() {
+        let grid = get_grid().map(|c| c.to_ascii_uppercase());
+
+        assert_eq!(grid.to_string(), "ABC\nDEF");
+        assert_eq!(Grid::<char>::default().to_string(), "");
+    }
+}
@@ -0,0 +1,636 @@
+use std::{collections::BTreeMap, fs, path::Path, str::FromStr};
+
+use crate::{
+    answer::Answer,
+    challenge::{ChallengeFactory, DEFAULT_YEAR},
+    error::ChallengeError,
+    runner::{self, PartResult, RunOptions, RunRow, RunStatus},
+    utils::{parse_toml_table, split_offsets},
+};
+
+pub const DEFAULT_TOML_PATH: &str = "./answers.toml";
+pub const DEFAULT_TEXT_PATH: &str = "./answers.txt";
+
+// Answers files written before years were supported start with the day, and
+// hold DEFAULT_YEAR's answers. Anything above the last day is a year.
+const LAST_DAY: u16 = 25;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    entries: BTreeMap<(u16, u8, u8), Answer>,
+    // Entries added by `record` since loading, which `save` adds to the file.
+    recorded: Vec<(u16, u8, u8)>,
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self, ChallengeError> {
+        if !path.exists() {
+            return Ok(Answers::default());
+        }
+
+        let content = fs::read_to_string(path).map_err(|e| {
+            ChallengeError::io(&format!("Could not read {}: {}", path.display(), e))
+        })?;
+        if is_toml(path) {
+            Answers::parse_toml(&content)
+        } else {
+            Answers::parse_text(&content)
+        }
+    }
+
+    // Adds the recorded answers to the file. Existing lines, comments and
+    // their order are left as they are.
+    pub fn save(&self, path: &Path) -> Result<(), ChallengeError> {
+        let content = if path.exists() {
+            fs::read_to_string(path).map_err(|e| {
+                ChallengeError::io(&format!("Could not read {}: {}", path.display(), e))
+            })?
+        } else {
+            String::new()
+        };
+        let content = if is_toml(path) {
+            self.append_toml(&content)?
+        } else {
+            self.append_text(&content)
+        };
+
+        fs::write(path, content)
+            .map_err(|e| ChallengeError::io(&format!("Could not write {}: {}", path.display(), e)))
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: u8) -> Option<&Answer> {
+        self.entries.get(&(year, day, part))
+    }
+
+    pub fn insert(&mut self, year: u16, day: u8, part: u8, answer: Answer) {
+        self.entries.insert((year, day, part), answer);
+    }
+
+    pub fn record(&mut self, year: u16, day: u8, part: u8, answer: Answer) {
+        self.insert(year, day, part, answer);
+        self.recorded.push((year, day, part));
+    }
+
+    // Days of the year with at least one stored answer.
+    pub fn days(&self, year: u16) -> Vec<u8> {
+        let mut days: Vec<u8> = self
+            .entries
+            .keys()
+            .filter(|(entry_year, _, _)| *entry_year == year)
+            .map(|(_, day, _)| *day)
+            .collect();
+        days.dedup();
+        days
+    }
+
+    // One answer per line: `<year> <day> <part> <answer>`. Blank lines and
+    // lines starting with `#` are ignored.
+    fn parse_text(content: &str) -> Result<Self, ChallengeError> {
+        let mut answers = Answers::default();
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim_end();
+            if line.trim_start().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+
+            let has_year = line
+                .split(' ')
+                .next()
+                .and_then(|field| field.parse::<u16>().ok())
+                .is_some_and(|field| field > LAST_DAY);
+            let mut fields = split_offsets(line, ' ');
+            let year = if has_year {
+                parse_field(fields.next(), "Could not parse year").map_err(|e| e.at_line(i + 1))?
+            } else {
+                DEFAULT_YEAR
+            };
+            let day =
+                parse_field(fields.next(), "Could not parse day").map_err(|e| e.at_line(i + 1))?;
+            let part_field = fields.next();
+            let part =
+                parse_field(part_field, "Could not parse part").map_err(|e| e.at_line(i + 1))?;
+            // The answer is the rest of the line, spaces included.
+            let answer = part_field
+                .and_then(|(offset, part)| line.get(offset + part.len() + 1..))
+                .filter(|answer| !answer.is_empty())
+                .ok_or_else(|| ChallengeError::parse(line, 1, "Missing answer"))
+                .map_err(|e| e.at_line(i + 1))?;
+            validate_part(line, part).map_err(|e| e.at_line(i + 1))?;
+
+            answers.insert(year, day, part, Answer::parse(answer));
+        }
+
+        Ok(answers)
+    }
+
+    // One table per year and day, e.g. `[2023.5]` followed by
+    // `part1 = "35"`.
+    fn parse_toml(content: &str) -> Result<Self, ChallengeError> {
+        let table = parse_toml_table(content)?;
+
+        let mut answers = Answers::default();
+        for (key, value) in &table {
+            let number = key
+                .parse::<u16>()
+                .map_err(|_| ChallengeError::parse(key, 1, "Could not parse year or day"))?;
+            if number <= LAST_DAY {
+                answers.insert_toml_day(DEFAULT_YEAR, key, value)?;
+                continue;
+            }
+
+            let days = value
+                .as_table()
+                .ok_or_else(|| ChallengeError::parse(key, 1, "Expected a table of days"))?;
+            for (day_key, parts) in days {
+                answers.insert_toml_day(number, day_key, parts)?;
+            }
+        }
+
+        Ok(answers)
+    }
+
+    fn insert_toml_day(
+        &mut self,
+        year: u16,
+        day_key: &str,
+        parts: &toml::Value,
+    ) -> Result<(), ChallengeError> {
+        let day = day_key
+            .parse::<u8>()
+            .map_err(|_| ChallengeError::parse(day_key, 1, "Could not parse day"))?;
+        let parts = parts
+            .as_table()
+            .ok_or_else(|| ChallengeError::parse(day_key, 1, "Expected a table of parts"))?;
+        for (part, answer) in parse_part_answers(parts)? {
+            self.insert(year, day, part, answer);
+        }
+
+        Ok(())
+    }
+
+    fn append_text(&self, content: &str) -> String {
+        let mut content = content.to_string();
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        for key @ (year, day, part) in &self.recorded {
+            content.push_str(&format!(
+                "{} {} {} {}\n",
+                year, day, part, self.entries[key]
+            ));
+        }
+
+        content
+    }
+
+    // New answers go into the day's existing table, which may be a legacy
+    // `[5]` table, or into a new `[2023.5]` table at the end.
+    fn append_toml(&self, content: &str) -> Result<String, ChallengeError> {
+        let mut document = content
+            .parse::<toml_edit::DocumentMut>()
+            .map_err(|e| ChallengeError::parse("", 1, e.message()))?;
+        for key @ (year, day, part) in &self.recorded {
+            let day_key = day.to_string();
+            let days = if *year == DEFAULT_YEAR && document.contains_key(&day_key) {
+                document.as_table_mut() as &mut dyn toml_edit::TableLike
+            } else {
+                let mut year_table = toml_edit::Table::new();
+                year_table.set_implicit(true);
+                let year_key = year.to_string();
+                document
+                    .entry(&year_key)
+                    .or_insert(toml_edit::Item::Table(year_table))
+                    .as_table_like_mut()
+                    .ok_or_else(|| {
+                        ChallengeError::parse(&year_key, 1, "Expected a table of days")
+                    })?
+            };
+            days.entry(&day_key)
+                .or_insert(toml_edit::table())
+                .as_table_like_mut()
+                .ok_or_else(|| ChallengeError::parse(&day_key, 1, "Expected a table of parts"))?
+                .insert(
+                    &format!("part{}", part),
+                    toml_edit::value(self.entries[key].to_string()),
+                );
+        }
+
+        Ok(document.to_string())
+    }
+}
+
+// Reads `part1 = "35"` style entries. Integers and strings are both accepted.
+pub(crate) fn parse_part_answers(parts: &toml::Table) -> Result<Vec<(u8, Answer)>, ChallengeError> {
+    let mut answers = Vec::new();
+    for (part_key, answer) in parts {
+        let part = part_key
+            .strip_prefix("part")
+            .and_then(|part| part.parse::<u8>().ok())
+            .ok_or_else(|| ChallengeError::parse(part_key, 1, "Could not parse part"))?;
+        validate_part(part_key, part)?;
+        let answer = match answer {
+            toml::Value::String(answer) => Answer::parse(answer),
+            toml::Value::Integer(answer) => Answer::from(*answer),
+            _ => {
+                return Err(ChallengeError::parse(
+                    &answer.to_string(),
+                    1,
+                    "Expected a string or integer answer",
+                ))
+            }
+        };
+        answers.push((part, answer));
+    }
+
+    Ok(answers)
+}
+
+fn is_toml(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "toml")
+}
+
+fn parse_field<T: FromStr>(
+    field: Option<(usize, &str)>,
+    message: &str,
+) -> Result<T, ChallengeError> {
+    let (offset, field) = field.unwrap_or_default();
+    field
+        .parse::<T>()
+        .map_err(|_| ChallengeError::parse(field, offset + 1, message))
+}
+
+fn validate_part(text: &str, part: u8) -> Result<(), ChallengeError> {
+    match part {
+        1 | 2 => Ok(()),
+        _ => Err(ChallengeError::parse(text, 1, "Part must be 1 or 2")),
+    }
+}
+
+pub fn default_answers_path() -> &'static str {
+    if Path::new(DEFAULT_TOML_PATH).exists() {
+        DEFAULT_TOML_PATH
+    } else {
+        DEFAULT_TEXT_PATH
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Pass,
+    Fail { expected: Answer, actual: Answer },
+    Missing,
+    Recorded,
+    Skipped(String),
+    // A stored answer for a day with no registered solver.
+    Unverified,
+    Failed(ChallengeError),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct PartCheck {
+    pub challenge: u8,
+    pub part: u8,
+    pub outcome: Outcome,
+}
+
+impl PartCheck {
+    pub fn is_failure(&self) -> bool {
+        matches!(self.outcome, Outcome::Fail { .. } | Outcome::Failed(_))
+    }
+}
+
+pub fn check(rows: &[RunRow], answers: &mut Answers, record: bool) -> Vec<PartCheck> {
+    let mut checks = Vec::new();
+    for row in rows {
+        match &row.status {
+            RunStatus::Solved(part_one, part_two) => {
+                for (part, result) in [(1, part_one), (2, part_two)] {
+                    let Some(result) = result else {
+                        continue;
+                    };
+                    let outcome =
+                        check_part(row.year, row.challenge, part, result, answers, record);
+                    checks.push(PartCheck {
+                        challenge: row.challenge,
+                        part,
+                        outcome,
+                    });
+                }
+            }
+            RunStatus::MissingInput(path) => {
+                for part in [1, 2] {
+                    checks.push(PartCheck {
+                        challenge: row.challenge,
+                        part,
+                        outcome: Outcome::Skipped(format!("missing input ({})", path)),
+                    });
+                }
+            }
+            RunStatus::NotImplemented => {
+                for part in [1, 2] {
+                    if answers.get(row.year, row.challenge, part).is_some() {
+                        checks.push(PartCheck {
+                            challenge: row.challenge,
+                            part,
+                            outcome: Outcome::Unverified,
+                        });
+                    }
+                }
+            }
+            RunStatus::Failed(error) => {
+                for part in [1, 2] {
+                    checks.push(PartCheck {
+                        challenge: row.challenge,
+                        part,
+                        outcome: Outcome::Failed(error.clone()),
+                    });
+                }
+            }
+        }
+    }
+
+    checks
+}
+
+fn check_part(
+    year: u16,
+    challenge: u8,
+    part: u8,
+    result: &PartResult,
+    answers: &mut Answers,
+    record: bool,
+) -> Outcome {
+    let actual = match &result.answer {
+        Ok(actual) => actual,
+        Err(error) => return Outcome::Failed(error.clone()),
+    };
+
+    match answers.get(year, challenge, part) {
+        Some(expected) if expected == actual => Outcome::Pass,
+        Some(expected) => Outcome::Fail {
+            expected: expected.clone(),
+            actual: actual.clone(),
+        },
+        None if !actual.is_implemented() => Outcome::Skipped(String::from("not implemented")),
+        None if record => {
+            answers.record(year, challenge, part, actual.clone());
+            Outcome::Recorded
+        }
+        None => Outcome::Missing,
+    }
+}
+
+// Without a selection, every registered day is checked, along with any
+// day that has stored answers but no solver.
+pub fn verify(
+    challenges: Option<Vec<u8>>,
+    options: &RunOptions,
+    answers_path: &Path,
+    record: bool,
+) -> Result<bool, ChallengeError> {
+    let mut answers = Answers::load(answers_path)?;
+    let challenges = challenges.unwrap_or_else(|| {
+        let mut challenges = ChallengeFactory::registered(options.year);
+        challenges.extend(answers.days(options.year));
+        challenges.sort();
+        challenges.dedup();
+        challenges
+    });
+    let rows: Vec<RunRow> = challenges
+        .into_iter()
+        .map(|challenge| runner::run(challenge, options))
+        .collect();
+    let checks = check(&rows, &mut answers, record);
+
+    if checks
+        .iter()
+        .any(|check| check.outcome == Outcome::Recorded)
+    {
+        answers.save(answers_path)?;
+    }
+
+    print_report(&checks);
+    Ok(!checks.iter().any(PartCheck::is_failure))
+}
+
+pub fn print_report(checks: &[PartCheck]) {
+    for check in checks {
+        let status = match &check.outcome {
+            Outcome::Pass => String::from("pass"),
+            Outcome::Fail { expected, actual } => {
+                format!("FAIL (expected {}, got {})", expected, actual)
+            }
+            Outcome::Missing => String::from("missing"),
+            Outcome::Recorded => String::from("recorded"),
+            Outcome::Skipped(reason) => format!("skipped, {}", reason),
+            Outcome::Unverified => String::from("unverified, no solver is registered"),
+            Outcome::Failed(error) => format!("FAIL ({})", error),
+        };
+        println!("Day {:>2} Part {}: {}", check.challenge, check.part, status);
+    }
+
+    let count = |predicate: fn(&Outcome) -> bool| {
+        checks
+            .iter()
+            .filter(|check| predicate(&check.outcome))
+            .count()
+    };
+    println!(
+        "{} passed, {} failed, {} missing, {} recorded, {} unverified",
+        count(|outcome| *outcome == Outcome::Pass),
+        count(|outcome| matches!(outcome, Outcome::Fail { .. } | Outcome::Failed(_))),
+        count(|outcome| *outcome == Outcome::Missing),
+        count(|outcome| *outcome == Outcome::Recorded),
+        count(|outcome| *outcome == Outcome::Unverified),
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::challenge::NOT_IMPLEMENTED;
+
+    fn solved_row(challenge: u8, part_one: &str, part_two: &str) -> RunRow {
+        let part = |answer: &str| PartResult {
+            answer: Ok(Answer::parse(answer)),
+            elapsed: Duration::ZERO,
+            cached: false,
+        };
+        RunRow {
+            year: 2023,
+            challenge,
+            profile: None,
+            input: None,
+            status: RunStatus::Solved(Some(part(part_one)), Some(part(part_two))),
+        }
+    }
+
+    #[test]
+    fn verify_parse_text() {
+        let answers =
+            Answers::parse_text("# day part answer\n5 1 35\n\n5 2 46\n7 1 Not a number\n").unwrap();
+
+        assert_eq!(answers.get(2023, 5, 1), Some(&Answer::from(35)));
+        assert_eq!(answers.get(2023, 5, 2), Some(&Answer::from(46)));
+        assert_eq!(answers.get(2023, 7, 1), Some(&Answer::from("Not a number")));
+        assert_eq!(answers.get(2023, 7, 2), None);
+    }
+
+    #[test]
+    fn verify_parse_text_bad_part() {
+        let error = Answers::parse_text("5 1 35\n5 x 46\n").unwrap_err();
+        assert_eq!(
+            error,
+            ChallengeError::parse("x", 3, "Could not parse part").at_line(2)
+        );
+
+        let error = Answers::parse_text("5 3 35\n").unwrap_err();
+        assert_eq!(
+            error,
+            ChallengeError::parse("5 3 35", 1, "Part must be 1 or 2").at_line(1)
+        );
+    }
+
+    #[test]
+    fn verify_parse_toml() {
+        let answers = Answers::parse_toml("[5]\npart1 = \"35\"\npart2 = 46\n").unwrap();
+
+        assert_eq!(answers.get(2023, 5, 1), Some(&Answer::from(35)));
+        assert_eq!(answers.get(2023, 5, 2), Some(&Answer::from(46)));
+    }
+
+    #[test]
+    fn verify_parse_years() {
+        let answers = Answers::parse_text("2022 5 1 CMZ\n5 1 35\n").unwrap();
+        assert_eq!(answers.get(2022, 5, 1), Some(&Answer::from("CMZ")));
+        assert_eq!(answers.get(2023, 5, 1), Some(&Answer::from(35)));
+
+        let answers =
+            Answers::parse_toml("[5]\npart1 = 35\n\n[2022.5]\npart1 = \"CMZ\"\n").unwrap();
+        assert_eq!(answers.get(2022, 5, 1), Some(&Answer::from("CMZ")));
+        assert_eq!(answers.get(2023, 5, 1), Some(&Answer::from(35)));
+    }
+
+    #[test]
+    fn verify_check_uses_row_year() {
+        let mut answers = Answers::default();
+        answers.insert(2022, 5, 1, Answer::from(35));
+
+        let checks = check(&[solved_row(5, "35", "46")], &mut answers, true);
+
+        assert_eq!(checks[0].outcome, Outcome::Recorded);
+        assert_eq!(answers.get(2023, 5, 1), Some(&Answer::from(35)));
+    }
+
+    #[test]
+    fn verify_round_trip() {
+        let mut answers = Answers::default();
+        answers.record(2023, 5, 1, Answer::from(35));
+        answers.record(2023, 10, 2, Answer::from("Some text"));
+        answers.record(2022, 10, 2, Answer::from(13140));
+
+        let parsed = Answers::parse_text(&answers.append_text("")).unwrap();
+        assert_eq!(parsed.entries, answers.entries);
+        let parsed = Answers::parse_toml(&answers.append_toml("").unwrap()).unwrap();
+        assert_eq!(parsed.entries, answers.entries);
+    }
+
+    #[test]
+    fn verify_record_keeps_existing_text() {
+        let content = "# day part answer\n5 2 46\n5 1 35";
+        let mut answers = Answers::parse_text(content).unwrap();
+        answers.record(2023, 6, 1, Answer::from(288));
+
+        assert_eq!(
+            answers.append_text(content),
+            "# day part answer\n5 2 46\n5 1 35\n2023 6 1 288\n"
+        );
+    }
+
+    #[test]
+    fn verify_record_keeps_existing_toml() {
+        let content = "# Legacy days\n[5]\npart1 = 35 # checked\n\n[2022.5]\npart1 = \"CMZ\"\n";
+        let mut answers = Answers::parse_toml(content).unwrap();
+        answers.record(2023, 5, 2, Answer::from(46));
+        answers.record(2023, 6, 1, Answer::from(288));
+
+        let updated = answers.append_toml(content).unwrap();
+        assert_eq!(
+            updated,
+            "# Legacy days\n[5]\npart1 = 35 # checked\npart2 = \"46\"\n\n[2022.5]\npart1 = \"CMZ\"\n\n[2023.6]\npart1 = \"288\"\n"
+        );
+        assert_eq!(
+            Answers::parse_toml(&updated).unwrap().entries,
+            answers.entries
+        );
+    }
+
+    #[test]
+    fn verify_reports_unregistered_days() {
+        let mut answers = Answers::default();
+        answers.insert(2023, 25, 1, Answer::from(1));
+        let row = RunRow {
+            year: 2023,
+            challenge: 25,
+            profile: None,
+            input: None,
+            status: RunStatus::NotImplemented,
+        };
+
+        let checks = check(&[row], &mut answers, true);
+
+        assert_eq!(
+            checks,
+            vec![PartCheck {
+                challenge: 25,
+                part: 1,
+                outcome: Outcome::Unverified,
+            }]
+        );
+        assert_eq!(answers.days(2023), vec![25]);
+    }
+
+    #[test]
+    fn verify_check_outcomes() {
+        let mut answers = Answers::default();
+        answers.insert(2023, 5, 1, Answer::from(35));
+        answers.insert(2023, 5, 2, Answer::from(47));
+
+        let checks = check(&[solved_row(5, "35", "46")], &mut answers, false);
+
+        assert_eq!(checks[0].outcome, Outcome::Pass);
+        assert_eq!(
+            checks[1].outcome,
+            Outcome::Fail {
+                expected: Answer::from(47),
+                actual: Answer::from(46)
+            }
+        );
+        assert!(checks[1].is_failure());
+    }
+
+    #[test]
+    fn verify_check_records_missing() {
+        let mut answers = Answers::default();
+
+        let checks = check(
+            &[solved_row(6, "288", NOT_IMPLEMENTED)],
+            &mut answers,
+            false,
+        );
+        assert_eq!(checks[0].outcome, Outcome::Missing);
+        assert!(!checks[0].is_failure());
+
+        let checks = check(&[solved_row(6, "288", NOT_IMPLEMENTED)], &mut answers, true);
+        assert_eq!(checks[0].outcome, Outcome::Recorded);
+        assert_eq!(
+            checks[1].outcome,
+            Outcome::Skipped(String::from("not implemented"))
+        );
+        assert_eq!(answers.get(2023, 6, 1), Some(&Answer::from(288)));
+        assert_eq!(answers.get(2023, 6, 2), None);
+    }
+}
@@ -0,0 +1,130 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+// Modification times of the watched files. Directories contribute each file
+// inside them, so adding or removing a file also counts as a change.
+#[derive(Debug, Default, PartialEq)]
+pub struct Snapshot {
+    modified: BTreeMap<PathBuf, Option<SystemTime>>,
+}
+
+impl Snapshot {
+    pub fn take(paths: &[PathBuf]) -> Self {
+        let mut modified = BTreeMap::new();
+        for path in paths {
+            if path.is_dir() {
+                let entries = fs::read_dir(path).into_iter().flatten().flatten();
+                for entry in entries {
+                    let entry = entry.path();
+                    if entry.is_file() {
+                        modified.insert(entry.clone(), modified_time(&entry));
+                    }
+                }
+            } else {
+                // A missing file is recorded too, so creating it triggers a run.
+                modified.insert(path.clone(), modified_time(path));
+            }
+        }
+
+        Snapshot { modified }
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+pub struct Watcher {
+    paths: Vec<PathBuf>,
+    snapshot: Snapshot,
+}
+
+impl Watcher {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        let snapshot = Snapshot::take(&paths);
+        Watcher { paths, snapshot }
+    }
+
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+
+    // Returns true when anything changed since the last poll.
+    pub fn poll(&mut self) -> bool {
+        let snapshot = Snapshot::take(&self.paths);
+        if snapshot == self.snapshot {
+            return false;
+        }
+
+        self.snapshot = snapshot;
+        true
+    }
+}
+
+pub fn watch(paths: Vec<PathBuf>, interval: Duration, mut run: impl FnMut(&[PathBuf])) -> ! {
+    let mut watcher = Watcher::new(paths);
+    run(watcher.paths());
+    loop {
+        thread::sleep(interval);
+        if watcher.poll() {
+            run(watcher.paths());
+        }
+    }
+}
+
+pub fn clear_screen() {
+    print!("\x1B[2J\x1B[H");
+    let _ = io::stdout().flush();
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+
+    use super::*;
+    use crate::utils::temp_dir::TempDir;
+
+    #[test]
+    fn watch_detects_modified_file() {
+        let dir = TempDir::new("watch-file");
+        let input = dir.join("1.aoc");
+        fs::write(&input, "1abc2").unwrap();
+        let mut watcher = Watcher::new(vec![input.clone()]);
+
+        assert!(!watcher.poll());
+
+        File::options()
+            .write(true)
+            .open(&input)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH)
+            .unwrap();
+        assert!(watcher.poll());
+        assert!(!watcher.poll());
+    }
+
+    #[test]
+    fn watch_detects_created_files() {
+        let dir = TempDir::new("watch-dir");
+        let input = dir.join("2.aoc");
+        let examples = dir.join("examples");
+        fs::create_dir(&examples).unwrap();
+        let mut watcher = Watcher::new(vec![input.clone(), examples.clone()]);
+
+        assert!(!watcher.poll());
+
+        fs::write(&input, "Game 1: 1 red").unwrap();
+        assert!(watcher.poll());
+
+        fs::write(examples.join("2-games.txt"), "Game 1: 1 red").unwrap();
+        assert!(watcher.poll());
+    }
+}
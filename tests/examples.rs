@@ -0,0 +1,2 @@
+// One test per example in examples/, generated by build.rs.
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));
@@ -0,0 +1,193 @@
+use advent_of_code_2023::{
+    challenge::{
+        camel_cards::{Card, HandType},
+        haunted_wasteland::HauntedWasteland,
+        if_you_give_a_seed_a_fertilizer::{Almanac, Resource},
+        mirage_maintenance::History,
+        pipe_maze::{PipeMap, PipeType},
+    },
+    Answer, Challenge, ChallengeError, ChallengeFactory, SolveContext,
+};
+
+const ALMANAC: &str = "\
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+fn lines(input: &str) -> Vec<String> {
+    input.lines().map(String::from).collect()
+}
+
+#[test]
+fn public_api_factory_solves_from_reader() {
+    let input = "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483";
+    let solution = ChallengeFactory::create(2023, &7, &mut input.as_bytes()).unwrap();
+
+    assert_eq!(
+        solution.solve_part_one(&SolveContext::default()).unwrap(),
+        "6440"
+    );
+    assert_eq!(
+        solution.solve_part_two(&SolveContext::default()).unwrap(),
+        "5905"
+    );
+}
+
+#[test]
+fn public_api_typed_answers() {
+    let input = ".....\n.S-7.\n.|.|.\n.L-J.\n.....";
+    let solution = ChallengeFactory::create(2023, &10, &mut input.as_bytes()).unwrap();
+
+    assert_eq!(
+        solution.solve_part_one(&SolveContext::default()).unwrap(),
+        Answer::from(4)
+    );
+    assert_eq!(
+        solution.solve_part_two(&SolveContext::default()).unwrap(),
+        Answer::NotImplemented
+    );
+}
+
+#[test]
+fn public_api_factory_normalizes_input() {
+    let input = "\u{feff}RL\r\n\r\nAAA = (BBB, CCC)  \r\nBBB = (DDD, EEE)\r\nCCC = (ZZZ, GGG)\r\nDDD = (DDD, DDD)\r\nEEE = (EEE, EEE)\r\nGGG = (GGG, GGG)\r\nZZZ = (ZZZ, ZZZ)\r\n\r\n\r\n";
+    let solution = ChallengeFactory::create(2023, &8, &mut input.as_bytes()).unwrap();
+
+    assert_eq!(
+        solution.solve_part_one(&SolveContext::default()).unwrap(),
+        "2"
+    );
+}
+
+#[test]
+fn public_api_factory_reports_day_in_errors() {
+    let input = "Game 1: 3 blue, 4 rde";
+    let error = ChallengeFactory::create(2023, &2, &mut input.as_bytes())
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        error,
+        ChallengeError::parse("rde", 19, "Unknown color")
+            .at_line(1)
+            .with_day(2)
+    );
+}
+
+#[test]
+fn public_api_factory_unregistered_day() {
+    let error = ChallengeFactory::create(2023, &25, &mut "".as_bytes())
+        .err()
+        .unwrap();
+
+    assert_eq!(
+        error,
+        ChallengeError::Unregistered {
+            year: 2023,
+            day: 25
+        }
+    );
+    assert!(!ChallengeFactory::registered(2023).contains(&25));
+}
+
+#[test]
+fn public_api_challenge_solver_selection() {
+    let input = "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\n22A = (22B, XXX)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22B, 22B)\nXXX = (XXX, XXX)";
+    let mut haunted_wasteland = HauntedWasteland::default();
+    haunted_wasteland.load_str(input).unwrap();
+
+    assert_eq!(haunted_wasteland.solvers(), vec!["lcm", "brute-force"]);
+    for solver in haunted_wasteland.solvers() {
+        haunted_wasteland.use_solver(solver).unwrap();
+        assert_eq!(
+            haunted_wasteland
+                .solve_part_two(&SolveContext::default())
+                .unwrap(),
+            "6"
+        );
+    }
+}
+
+#[test]
+fn public_api_almanac_condense() {
+    let almanac = Almanac::new(lines(ALMANAC)).unwrap();
+
+    assert_eq!(
+        almanac.map_through(79.into(), Resource::Seed),
+        (82.into(), Resource::Location)
+    );
+
+    let condensed = almanac.condense().unwrap();
+    assert_eq!(condensed.get_from(), Resource::Seed);
+    assert_eq!(condensed.get_to(), Resource::Location);
+    assert_eq!(
+        condensed.lowest_overlap(&82.into(), &1.into()),
+        Some(46.into())
+    );
+    assert_eq!(condensed.lowest_overlap(&82.into(), &0.into()), None);
+}
+
+#[test]
+fn public_api_hand_type_parse() {
+    let cards: [Card; 5] = "KTJJT"
+        .chars()
+        .map(|c| Card::from(c).unwrap())
+        .collect::<Vec<Card>>()
+        .try_into()
+        .unwrap();
+    assert_eq!(HandType::parse(&cards), HandType::TwoPair);
+
+    let cards: [Card; 5] = "KTJJT"
+        .chars()
+        .map(|c| Card::from_with_joker(c).unwrap())
+        .collect::<Vec<Card>>()
+        .try_into()
+        .unwrap();
+    assert_eq!(HandType::parse(&cards), HandType::FourOfAKind);
+}
+
+#[test]
+fn public_api_history_extrapolate() {
+    let history = History::new("10 13 16 21 30 45").unwrap();
+
+    assert_eq!(history.extrapolate_next().unwrap(), 68);
+    assert_eq!(history.extrapolate_prev().unwrap(), 5);
+}
+
+#[test]
+fn public_api_pipe_map_loop() {
+    let pipe_map = PipeMap::new(lines(".....\n.S-7.\n.|.|.\n.L-J.\n.....")).unwrap();
+
+    let pipe_loop: Vec<(usize, usize)> = pipe_map.into_iter().map(|pipe| pipe.position).collect();
+    assert_eq!(pipe_loop.len(), 8);
+    assert_eq!(pipe_loop[0], (1, 1));
+    assert_eq!(pipe_map.at(1, 1).pipe_type, PipeType::Start);
+}